@@ -1,29 +1,159 @@
-use crate::{document::DocumentCommand, q2::Q2Command, q3::Q3Command};
+use std::{env, fs::File, io::Write, time::Duration};
+
+use crate::{
+    common::{
+        execution::ExecutionPolicy,
+        progress::ProgressMode,
+        random,
+        run_report::RunReport,
+        stopping,
+        store::{ResultStore, RunRecord},
+    },
+    document::DocumentCommand,
+    error::{CliError, EXIT_CODES_HELP},
+    history::HistoryCommand,
+    plot::PlotCommand,
+    q2::Q2Command,
+    q3::Q3Command,
+    report::ReportCommand,
+    rnla::RnlaCommand,
+    run::RunCommand,
+};
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{ArgEnum, Parser, Subcommand};
 use env_logger::Env;
+use log::warn;
+use rayon::ThreadPoolBuilder;
+use serde::Serialize;
 
 use super::q1::Q1Command;
 
 #[derive(Parser)]
-#[clap(author, version, about)]
+#[clap(author, version, about, after_help = EXIT_CODES_HELP)]
 pub struct CommandLineArguments {
     #[clap(short, long)]
     debug: bool,
+    ///Format of the run report
+    #[clap(short, long, arg_enum, default_value = "text")]
+    format: OutputFormat,
+    ///Path the run report will be written to instead of stdout
+    #[clap(short, long)]
+    output: Option<String>,
+    ///How the progress of nested repetitions is reported
+    #[clap(long, arg_enum, default_value = "auto")]
+    progress: ProgressMode,
+    ///Seconds after which no new repetitions are started and the completed ones are reduced
+    #[clap(long)]
+    time_budget: Option<f64>,
+    ///Amount of threads repetitions run on, defaults to the amount of logical cores
+    #[clap(short, long)]
+    threads: Option<usize>,
+    ///How repetitions are scheduled on the threads
+    #[clap(long, arg_enum, default_value = "parallel")]
+    execution: ExecutionPolicy,
+    ///Seed of the random generators, runs with the same seed and parameters give the same results
+    #[clap(long)]
+    seed: Option<u64>,
+    ///Path of the JSON-lines file every run is recorded to and the history is read from
+    #[clap(long, default_value = "results.jsonl")]
+    store: String,
+    ///Do not record the run in the results store
+    #[clap(long)]
+    no_store: bool,
     #[clap(subcommand)]
     command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, ArgEnum)]
+enum OutputFormat {
+    ///Timing summary in the log only
+    Text,
+    ///Run report including the result and the command parameters as JSON
+    Json,
+}
+
 impl CommandLineArguments {
     pub fn invoke(&self) -> Result<()> {
         let env = Env::new().default_filter_or(if self.debug { "debug" } else { "info" });
         env_logger::init_from_env(env);
+        self.progress.set();
+
+        if let Some(time_budget) = self.time_budget {
+            if !time_budget.is_finite() || time_budget <= 0.0 {
+                return Err(CliError::invalid_parameter(
+                    "time-budget",
+                    time_budget,
+                    "must be a positive number of seconds",
+                )
+                .into());
+            }
+        }
+
+        stopping::install(self.time_budget.map(Duration::from_secs_f64))?;
+        self.execution.set();
+
+        if let Some(seed) = self.seed {
+            random::set_seed(seed);
+        }
+
+        if self.threads == Some(0) {
+            return Err(
+                CliError::invalid_parameter("threads", 0, "must be at least 1 thread").into(),
+            );
+        }
+
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(self.threads.unwrap_or_default())
+            .build()?;
+
+        let store = ResultStore::new(&self.store);
+
+        // the command itself draws from the stream of the seed whichever worker runs it
+        let report =
+            pool.install(|| random::scoped(random::seed(), || self.command.invoke(&store)));
 
-        self.command.invoke()
+        if let Some(mut report) = report? {
+            if report.parameters.is_null() {
+                report.parameters = serde_json::to_value(&self.command)?;
+            }
+
+            self.write_report(&report)?;
+            self.record(&store, report);
+        }
+
+        Ok(())
+    }
+
+    /// A run that cannot be recorded is only reported, its results were already written
+    fn record(&self, store: &ResultStore, report: RunReport) {
+        if self.no_store {
+            return;
+        }
+
+        let command = env::args().skip(1).collect::<Vec<String>>().join(" ");
+
+        if let Err(error) = store.append(&RunRecord::new(command, report)) {
+            warn!("run was not recorded [error={:#}]", error);
+        }
+    }
+
+    fn write_report(&self, report: &RunReport) -> Result<()> {
+        // a destination of the report implies it is wanted as JSON
+        let content = match (self.format, &report.destination) {
+            (OutputFormat::Text, None) => return Ok(()),
+            _ => serde_json::to_string_pretty(report)?,
+        };
+
+        match self.output.as_ref().or(report.destination.as_ref()) {
+            Some(path) => writeln!(File::create(path)?, "{}", content)?,
+            None => println!("{}", content),
+        }
+
+        Ok(())
     }
 }
 
-#[derive(Debug, Subcommand)]
+#[derive(Debug, Subcommand, Serialize)]
 enum Commands {
     ///Run Question1 program
     Q1(Q1Command),
@@ -31,17 +161,32 @@ enum Commands {
     Q2(Q2Command),
     ///Run Question3 program
     Q3(Q3Command),
+    ///Run randomized numerical linear algebra programs
+    Rnla(RnlaCommand),
+    ///Run a registered algorithm by name or an experiment described by a TOML or JSON file
+    Run(RunCommand),
+    ///List, show and diff the runs recorded in the results store
+    History(HistoryCommand),
+    ///Render SVG charts of success rates, estimate distributions and the secretary strategy
+    Plot(PlotCommand),
+    ///Write a Markdown or HTML report of the latest recorded run of every question
+    Report(ReportCommand),
     ///Run Documentation process
     Document(DocumentCommand),
 }
 
 impl Commands {
-    pub fn invoke(&self) -> Result<()> {
+    pub fn invoke(&self, store: &ResultStore) -> Result<Option<RunReport>> {
         match self {
-            Commands::Q1(command) => command.invoke(),
-            Commands::Q2(command) => command.invoke(),
-            Commands::Q3(command) => command.invoke(),
-            Commands::Document(command) => command.invoke(),
+            Commands::Q1(command) => command.invoke().map(Some),
+            Commands::Q2(command) => command.invoke().map(Some),
+            Commands::Q3(command) => command.invoke().map(Some),
+            Commands::Rnla(command) => command.invoke().map(Some),
+            Commands::Run(command) => command.invoke(),
+            Commands::History(command) => command.invoke(store).map(|_| None),
+            Commands::Plot(command) => command.invoke(store).map(|_| None),
+            Commands::Report(command) => command.invoke(store).map(|_| None),
+            Commands::Document(command) => command.invoke().map(|_| None),
         }
     }
 }
@@ -1,12 +1,22 @@
-use std::{fmt::Debug};
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
-use indicatif::{ProgressBar, ProgressStyle};
+use cpu_time::ProcessTime;
 use log::{info, debug};
 use nameof::name_of;
+use serde::Serialize;
+
+use super::{
+    progress::{Progress, ProgressLevel},
+    run_report::RunReport,
+};
 
 pub trait Algorithm: Sized {
-    type Input: Debug + Clone;
+    type Input: Debug + Clone + Sync + Send;
     type Output: Debug + Sync + Send;
 
     fn name(&self) -> String;
@@ -16,23 +26,57 @@ pub trait Algorithm: Sized {
         1
     }
 
+    /// Duration of every outermost repetition of the last run, empty when the algorithm does not repeat
+    fn latencies(&self) -> Vec<Duration> {
+        Vec::new()
+    }
+
+    /// Name and repetition count of every nested repetition level, outermost first
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        Vec::new()
+    }
+
+    /// Hands every nested repetition level its progress, outermost first
+    fn attach_progress(&self, _levels: &[Arc<ProgressLevel>]) {}
+
+    /// Marks the outermost repetition, it may reduce over the repetitions completed before a stop
+    /// while inner repetitions fail as a whole, and it alone runs in parallel under
+    /// [`ParallelOuterOnly`](super::execution::ExecutionPolicy::ParallelOuterOnly)
+    fn mark_outermost(&self) {}
+
+    /// Whether the last run was stopped before completing every repetition
+    fn is_partial(&self) -> bool {
+        false
+    }
+
     fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output>;
 
-    fn run(&self) -> Result<()> {
+    /// Runs the algorithm as the outermost one with progress reporting and returns its output as is
+    fn run_output(&self) -> Result<Self::Output> {
         info!("{} started", self.name());
         debug!("{}={:?}", "input", self.input());
 
-        let progress = ProgressBar::new(self.get_repetitions())
-            .with_style(
-                ProgressStyle::default_bar()
-                    .template("[{elapsed_precise}] {bar:100.green} {percent:>3}%")
-                    .progress_chars("##-"),
-            )
-            .with_message(self.name());
+        // without nested repetitions the algorithm reports its own progress as a single level
+        let mut levels = self.progress_levels();
+        let single_level = levels.is_empty();
+
+        if single_level {
+            levels.push((self.name(), self.get_repetitions()));
+        }
+
+        let progress = Progress::new(levels);
+        self.attach_progress(progress.levels());
+        self.mark_outermost();
+
+        let result = self.run_internal(|| {
+            if single_level {
+                progress.levels()[0].inc();
+            }
+        });
 
-        let result = self.run_internal(|| progress.inc(1))?;
+        progress.finish();
 
-        progress.finish_and_clear();
+        let result = result?;
         info!(
             "{} finished [{}={:?}]",
             self.name(),
@@ -40,6 +84,40 @@ pub trait Algorithm: Sized {
             result
         );
 
-        Ok(())
+        Ok(result)
+    }
+
+    fn run(&self) -> Result<RunReport>
+    where
+        Self::Output: Serialize,
+    {
+        let start = Instant::now();
+        let cpu_start = ProcessTime::now();
+
+        let result = self.run_output()?;
+
+        let wall_time = start.elapsed();
+        let cpu_time = cpu_start.elapsed();
+
+        let report = RunReport::new(
+            self.name(),
+            &result,
+            self.is_partial(),
+            wall_time,
+            cpu_time,
+            self.latencies(),
+        )?;
+
+        info!(
+            "{} report [wall_time={:?} cpu_time={:?} throughput={:.2}/s threads={} partial={}]",
+            self.name(),
+            wall_time,
+            cpu_time,
+            report.throughput,
+            report.threads,
+            report.partial
+        );
+
+        Ok(report)
     }
 }
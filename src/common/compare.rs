@@ -0,0 +1,130 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use cpu_time::ProcessTime;
+use log::info;
+use serde::Serialize;
+
+use super::{
+    algorithm::Algorithm,
+    run_report::RunReport,
+    table::{format_cell, format_table},
+};
+
+type Entry = Box<dyn Fn() -> Result<RunReport>>;
+
+/// Runs several algorithms one after the other and lays their results side by side, one row
+/// per algorithm and one column per result field any of them has
+pub struct Compare {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComparisonRow {
+    pub algorithm: String,
+    pub result: serde_json::Value,
+    pub partial: bool,
+    pub wall_time: f64,
+    pub repetitions: usize,
+    pub throughput: f64,
+}
+
+impl Compare {
+    pub fn new(name: String) -> Self {
+        Compare {
+            name,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds a row named after the algorithm
+    pub fn add<TAlgorithm>(mut self, algorithm: TAlgorithm) -> Self
+    where
+        TAlgorithm: Algorithm + 'static,
+        TAlgorithm::Output: Serialize,
+    {
+        self.entries.push(Box::new(move || algorithm.run()));
+        self
+    }
+
+    pub fn run(self) -> Result<RunReport> {
+        info!("{} started", self.name);
+
+        let start = Instant::now();
+        let cpu_start = ProcessTime::now();
+
+        let rows = self
+            .entries
+            .iter()
+            .map(|run| {
+                let report = run()?;
+
+                Ok(ComparisonRow {
+                    algorithm: report.algorithm,
+                    result: report.result,
+                    partial: report.partial,
+                    wall_time: report.wall_time,
+                    repetitions: report.repetitions,
+                    throughput: report.throughput,
+                })
+            })
+            .collect::<Result<Vec<ComparisonRow>>>()?;
+
+        let wall_time = start.elapsed();
+        let cpu_time = cpu_start.elapsed();
+
+        info!("{} finished\n{}", self.name, table(&rows));
+
+        RunReport::new(
+            self.name,
+            &rows,
+            rows.iter().any(|row| row.partial),
+            wall_time,
+            cpu_time,
+            rows.iter()
+                .map(|row| Duration::from_secs_f64(row.wall_time))
+                .collect(),
+        )
+    }
+}
+
+/// Aligned text table with a column per result field followed by the timing columns
+fn table(rows: &[ComparisonRow]) -> String {
+    let mut fields = Vec::new();
+
+    for row in rows {
+        if let Some(result) = row.result.as_object() {
+            for field in result.keys() {
+                if !fields.contains(field) {
+                    fields.push(field.clone());
+                }
+            }
+        }
+    }
+
+    let header = ["algorithm".to_string()]
+        .into_iter()
+        .chain(fields.iter().cloned())
+        .chain(["wall_time".to_string(), "throughput".to_string()])
+        .collect::<Vec<String>>();
+
+    let lines = rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.algorithm.clone()];
+
+            cells.extend(
+                fields
+                    .iter()
+                    .map(|field| format_cell(row.result.get(field))),
+            );
+            cells.push(format!("{:.3}s", row.wall_time));
+            cells.push(format!("{:.2}/s", row.throughput));
+
+            cells
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    format_table(&header, &lines)
+}
@@ -0,0 +1,110 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{algorithm::Algorithm, progress::ProgressLevel};
+
+/// Object-safe counterpart of [`Algorithm`], the input is rendered with `Debug` and the output
+/// serialized to JSON so algorithms of different types can be stored and selected at runtime
+pub trait DynAlgorithm: Sync + Send {
+    fn dyn_name(&self) -> String;
+    fn dyn_input(&self) -> String;
+    fn dyn_get_repetitions(&self) -> u64;
+    fn dyn_progress_levels(&self) -> Vec<(String, u64)>;
+    fn dyn_attach_progress(&self, levels: &[Arc<ProgressLevel>]);
+    fn dyn_mark_outermost(&self);
+    fn dyn_is_partial(&self) -> bool;
+    fn dyn_latencies(&self) -> Vec<Duration>;
+    fn dyn_run_internal(
+        &self,
+        update_progress: &(dyn Fn() + Sync + Send),
+    ) -> Result<serde_json::Value>;
+}
+
+impl<TAlgorithm> DynAlgorithm for TAlgorithm
+where
+    TAlgorithm: Algorithm + Sync + Send,
+    TAlgorithm::Output: Serialize,
+{
+    fn dyn_name(&self) -> String {
+        self.name()
+    }
+
+    fn dyn_input(&self) -> String {
+        format!("{:?}", self.input())
+    }
+
+    fn dyn_get_repetitions(&self) -> u64 {
+        self.get_repetitions()
+    }
+
+    fn dyn_progress_levels(&self) -> Vec<(String, u64)> {
+        self.progress_levels()
+    }
+
+    fn dyn_attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        self.attach_progress(levels)
+    }
+
+    fn dyn_mark_outermost(&self) {
+        self.mark_outermost()
+    }
+
+    fn dyn_is_partial(&self) -> bool {
+        self.is_partial()
+    }
+
+    fn dyn_latencies(&self) -> Vec<Duration> {
+        self.latencies()
+    }
+
+    fn dyn_run_internal(
+        &self,
+        update_progress: &(dyn Fn() + Sync + Send),
+    ) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(self.run_internal(update_progress)?)?)
+    }
+}
+
+/// Lets a type-erased algorithm take part in the combinators again
+impl Algorithm for Box<dyn DynAlgorithm> {
+    type Input = String;
+    type Output = serde_json::Value;
+
+    fn name(&self) -> String {
+        self.as_ref().dyn_name()
+    }
+
+    fn input(&self) -> Self::Input {
+        self.as_ref().dyn_input()
+    }
+
+    fn get_repetitions(&self) -> u64 {
+        self.as_ref().dyn_get_repetitions()
+    }
+
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        self.as_ref().dyn_progress_levels()
+    }
+
+    fn attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        self.as_ref().dyn_attach_progress(levels)
+    }
+
+    fn mark_outermost(&self) {
+        self.as_ref().dyn_mark_outermost()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.as_ref().dyn_is_partial()
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        self.as_ref().dyn_latencies()
+    }
+
+    fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
+        self.as_ref().dyn_run_internal(&update_progress)
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use clap::ArgEnum;
+use serde::Serialize;
+
+static POLICY: AtomicU8 = AtomicU8::new(ExecutionPolicy::Parallel as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum, Serialize)]
+pub enum ExecutionPolicy {
+    ///Every repetition runs on the calling thread, for debugging and profiling
+    Sequential,
+    ///Repetitions of every nesting level run on the thread pool
+    Parallel,
+    ///Only the outermost repetitions run on the thread pool, nested ones run sequentially
+    ParallelOuterOnly,
+}
+
+impl ExecutionPolicy {
+    /// Sets the policy of every repetition without a policy of its own
+    pub fn set(self) {
+        POLICY.store(self as u8, Ordering::Relaxed);
+    }
+
+    pub fn current() -> Self {
+        match POLICY.load(Ordering::Relaxed) {
+            policy if policy == ExecutionPolicy::Sequential as u8 => ExecutionPolicy::Sequential,
+            policy if policy == ExecutionPolicy::ParallelOuterOnly as u8 => {
+                ExecutionPolicy::ParallelOuterOnly
+            }
+            _ => ExecutionPolicy::Parallel,
+        }
+    }
+
+    /// Whether a repetition with its own `policy` runs in parallel, a sequential global policy
+    /// overrides every repetition
+    pub fn is_parallel(policy: Option<ExecutionPolicy>, outermost: bool) -> bool {
+        let global = ExecutionPolicy::current();
+
+        if global == ExecutionPolicy::Sequential {
+            return false;
+        }
+
+        match policy.unwrap_or(global) {
+            ExecutionPolicy::Sequential => false,
+            ExecutionPolicy::Parallel => true,
+            ExecutionPolicy::ParallelOuterOnly => outermost,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::error::CliError;
+
+use super::registry::Parameters;
+
+/// Run of a registered algorithm described by a TOML or JSON file, e.g.
+///
+/// ```toml
+/// algorithm = "q2-amplificated"
+/// seed = 42
+/// output = "q2-amplificated.json"
+///
+/// [parameters]
+/// epsilon = 0.25
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Experiment {
+    /// Name of the registered algorithm
+    pub algorithm: String,
+    /// Parameters of the algorithm by field or flag name, the others keep their defaults
+    #[serde(default)]
+    pub parameters: Parameters,
+    pub seed: Option<u64>,
+    /// Path the JSON run report will be written to
+    pub output: Option<String>,
+}
+
+impl Experiment {
+    /// Whether `target` names an experiment file rather than a registered algorithm
+    pub fn is_experiment_file(target: &str) -> bool {
+        matches!(
+            Path::new(target)
+                .extension()
+                .and_then(|extension| extension.to_str()),
+            Some("toml") | Some("json")
+        )
+    }
+
+    /// Reads an experiment in the format matching the file extension (.toml, .json)
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("cannot open experiment file [path={}]", path))?;
+
+        let experiment = if path.ends_with(".toml") {
+            toml::from_str(&content).map_err(|error| error.to_string())
+        } else {
+            serde_json::from_str(&content).map_err(|error| error.to_string())
+        };
+
+        experiment.map_err(|reason| CliError::invalid_parameter("experiment", path, &reason).into())
+    }
+}
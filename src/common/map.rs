@@ -0,0 +1,74 @@
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use anyhow::Result;
+
+use super::{algorithm::Algorithm, progress::ProgressLevel};
+
+pub struct Map<TAlgorithm, TMapper> {
+    algorithm: TAlgorithm,
+    mapper: TMapper,
+}
+
+impl<TAlgorithm, TResult: Debug + Sync + Send, TMapper> Algorithm for Map<TAlgorithm, TMapper>
+where
+    TAlgorithm: Algorithm,
+    TMapper: Fn(TAlgorithm::Output) -> TResult,
+{
+    type Input = TAlgorithm::Input;
+    type Output = TResult;
+
+    fn name(&self) -> String {
+        format!("{} mapped", self.algorithm.name())
+    }
+
+    fn input(&self) -> Self::Input {
+        self.algorithm.input()
+    }
+
+    fn get_repetitions(&self) -> u64 {
+        self.algorithm.get_repetitions()
+    }
+
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        self.algorithm.progress_levels()
+    }
+
+    fn attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        self.algorithm.attach_progress(levels)
+    }
+
+    fn mark_outermost(&self) {
+        self.algorithm.mark_outermost()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.algorithm.is_partial()
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        self.algorithm.latencies()
+    }
+
+    fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
+        Ok((self.mapper)(self.algorithm.run_internal(update_progress)?))
+    }
+}
+
+pub trait IntoMap<TAlgorithm: Algorithm> {
+    /// Transforms the output of the algorithm, use `reduce` for transformations that may fail
+    fn map<TMapper, TResult>(self, mapper: TMapper) -> Map<TAlgorithm, TMapper>
+    where
+        TMapper: Fn(TAlgorithm::Output) -> TResult;
+}
+
+impl<TAlgorithm: Algorithm> IntoMap<TAlgorithm> for TAlgorithm {
+    fn map<TMapper, TResult>(self, mapper: TMapper) -> Map<TAlgorithm, TMapper>
+    where
+        TMapper: Fn(TAlgorithm::Output) -> TResult,
+    {
+        Map {
+            algorithm: self,
+            mapper,
+        }
+    }
+}
@@ -1,4 +1,19 @@
 pub mod reduce;
 pub mod algorithm;
+pub mod compare;
+pub mod dyn_algorithm;
+pub mod execution;
+pub mod experiment;
+pub mod map;
+pub mod progress;
+pub mod random;
+pub mod registry;
 pub mod repeat;
-pub mod with_name;
\ No newline at end of file
+pub mod run_report;
+pub mod stopping;
+pub mod store;
+pub mod table;
+pub mod tolerate_failures;
+pub mod validation;
+pub mod with_name;
+pub mod zip;
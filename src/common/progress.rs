@@ -0,0 +1,146 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicU8, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+use clap::ArgEnum;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use log::info;
+use serde::Serialize;
+
+/// Percent step between two log lines in [`ProgressMode::Log`]
+const LOG_STEP_PERCENT: u64 = 10;
+
+static MODE: AtomicU8 = AtomicU8::new(ProgressMode::Auto as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum, Serialize)]
+pub enum ProgressMode {
+    ///Bars when stderr is a terminal, log lines otherwise
+    Auto,
+    ///One progress bar per nesting level
+    Bars,
+    ///Periodic log lines per nesting level
+    Log,
+    ///No progress reporting
+    Off,
+}
+
+impl ProgressMode {
+    /// Sets the mode used by every following [`Progress`]
+    pub fn set(self) {
+        MODE.store(self as u8, Ordering::Relaxed);
+    }
+
+    fn current() -> Self {
+        match MODE.load(Ordering::Relaxed) {
+            mode if mode == ProgressMode::Bars as u8 => ProgressMode::Bars,
+            mode if mode == ProgressMode::Log as u8 => ProgressMode::Log,
+            mode if mode == ProgressMode::Off as u8 => ProgressMode::Off,
+            _ if ProgressBar::new(0).is_hidden() => ProgressMode::Log,
+            _ => ProgressMode::Bars,
+        }
+    }
+}
+
+/// Progress of one nesting level, shared by every repetition of that level
+pub struct ProgressLevel {
+    name: String,
+    bar: ProgressBar,
+    log: bool,
+    logged_percent: AtomicU64,
+}
+
+impl ProgressLevel {
+    pub fn inc(&self) {
+        self.bar.inc(1);
+
+        if !self.log || self.bar.length() == 0 {
+            return;
+        }
+
+        let percent = self.bar.position() * 100 / self.bar.length();
+        let logged_percent = self.logged_percent.load(Ordering::Relaxed);
+
+        if percent >= logged_percent + LOG_STEP_PERCENT
+            && self
+                .logged_percent
+                .compare_exchange(logged_percent, percent, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            info!(
+                "{} progress [percent={} position={}/{} eta={:?}]",
+                self.name,
+                percent,
+                self.bar.position(),
+                self.bar.length(),
+                self.bar.eta()
+            );
+        }
+    }
+}
+
+/// Progress of a whole run with one level per nested repetition, outermost first
+pub struct Progress {
+    levels: Vec<Arc<ProgressLevel>>,
+    drawer: Option<JoinHandle<()>>,
+}
+
+impl Progress {
+    /// `levels` holds the name and repetition count of every level, a level's total
+    /// is the product of its own count with the counts of all outer levels
+    pub fn new(levels: Vec<(String, u64)>) -> Self {
+        let mode = ProgressMode::current();
+        let multi = MultiProgress::new();
+
+        let style = ProgressStyle::default_bar()
+            .template("{prefix:>40} [{elapsed_precise}] {bar:60.green} {percent:>3}% eta {eta}")
+            .progress_chars("##-");
+
+        let mut total = 1;
+        let levels = levels
+            .into_iter()
+            .map(|(name, count)| {
+                total *= count;
+
+                let bar = match mode {
+                    ProgressMode::Bars => multi.add(ProgressBar::new(total)),
+                    _ => ProgressBar::with_draw_target(total, ProgressDrawTarget::hidden()),
+                };
+
+                Arc::new(ProgressLevel {
+                    bar: bar.with_style(style.clone()).with_prefix(name.clone()),
+                    name,
+                    log: mode == ProgressMode::Log,
+                    logged_percent: AtomicU64::new(0),
+                })
+            })
+            .collect::<Vec<Arc<ProgressLevel>>>();
+
+        // the bars of a MultiProgress are only drawn while it is joined
+        let drawer = match mode {
+            ProgressMode::Bars if !levels.is_empty() => Some(thread::spawn(move || {
+                multi.join_and_clear().ok();
+            })),
+            _ => None,
+        };
+
+        Progress { levels, drawer }
+    }
+
+    pub fn levels(&self) -> &[Arc<ProgressLevel>] {
+        &self.levels
+    }
+
+    pub fn finish(self) {
+        for level in self.levels.iter() {
+            level.bar.finish_and_clear();
+        }
+
+        if let Some(drawer) = self.drawer {
+            drawer.join().ok();
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::{cell::RefCell, sync::Mutex};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+static SEED: Mutex<Option<u64>> = Mutex::new(None);
+
+thread_local! {
+    /// Generator of the repetition running on this thread, `None` when runs are not seeded
+    static GENERATOR: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Makes every following run reproducible, each repetition draws from its own generator
+/// seeded by the enclosing one so the results do not depend on the thread scheduling
+pub fn set_seed(seed: u64) {
+    if let Ok(mut stored) = SEED.lock() {
+        *stored = Some(seed);
+    }
+}
+
+/// Seed set by [`set_seed`], `None` when runs are not reproducible
+pub fn seed() -> Option<u64> {
+    SEED.lock().ok().and_then(|seed| *seed)
+}
+
+/// Random generator for a single use, seeded from the running repetition when a seed is set
+///
+/// # Panics
+///
+/// When a seed is set and the caller is outside of [`scoped`], such a generator would depend
+/// on the thread scheduling
+pub fn rng() -> StdRng {
+    with_generator(|generator| StdRng::seed_from_u64(generator.gen()))
+        .unwrap_or_else(StdRng::from_entropy)
+}
+
+/// Seeds of `count` repetitions, `None` when runs are not seeded
+pub fn repetition_seeds(count: usize) -> Option<Vec<u64>> {
+    with_generator(|generator| (0..count).map(|_| generator.gen()).collect())
+}
+
+/// Runs `action` with the generator of the repetition seeded by `seed`
+pub fn scoped<T, F: FnOnce() -> T>(seed: Option<u64>, action: F) -> T {
+    let seed = match seed {
+        Some(seed) => seed,
+        None => return action(),
+    };
+
+    let enclosing =
+        GENERATOR.with(|generator| generator.replace(Some(StdRng::seed_from_u64(seed))));
+    let result = action();
+
+    GENERATOR.with(|generator| *generator.borrow_mut() = enclosing);
+
+    result
+}
+
+fn with_generator<T, F: FnOnce(&mut StdRng) -> T>(action: F) -> Option<T> {
+    GENERATOR.with(|generator| {
+        let mut generator = generator.borrow_mut();
+
+        // which worker picks up unscoped work is up to the scheduler, so seeded runs only draw
+        // from the generator of the enclosing scope
+        if generator.is_none() && seed().is_some() {
+            panic!("seeded random generation outside of `random::scoped` is not reproducible");
+        }
+
+        generator.as_mut().map(action)
+    })
+}
@@ -1,18 +1,20 @@
-use std::{fmt::Debug};
+use std::{fmt::Debug, sync::Arc, time::Duration};
 
 use anyhow::{Ok, Result};
 
-use super::{algorithm::Algorithm, repeat::Repeat};
+use super::{algorithm::Algorithm, progress::ProgressLevel};
 
-pub struct Reduce<TAlgorithm, TReducer> {
+pub struct Reduce<TAlgorithm: Algorithm, TReducer> {
     algorithm: TAlgorithm,
     reducer: TReducer,
+    /// Taken once, a reduce inside a repeat runs on every repetition and the input may be large
+    input: TAlgorithm::Input,
 }
 
 impl<TAlgorithm, TResult : Debug + Sync + Send, TReducer> Algorithm for Reduce<TAlgorithm, TReducer>
 where
     TAlgorithm: Algorithm,
-    TReducer: Fn(TAlgorithm::Output) -> Result<TResult>,
+    TReducer: Fn(TAlgorithm::Output, &TAlgorithm::Input) -> Result<TResult>,
 {
     type Input = TAlgorithm::Input;
     type Output = TResult;
@@ -22,30 +24,54 @@ where
     }
 
     fn input(&self) -> Self::Input {
-        self.algorithm.input().clone()
+        self.input.clone()
     }
 
     fn get_repetitions(&self) -> u64 {
         self.algorithm.get_repetitions()
     }
 
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        self.algorithm.progress_levels()
+    }
+
+    fn attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        self.algorithm.attach_progress(levels)
+    }
+
+    fn mark_outermost(&self) {
+        self.algorithm.mark_outermost()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.algorithm.is_partial()
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        self.algorithm.latencies()
+    }
+
     fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
-        Ok((self.reducer)(self.algorithm.run_internal(update_progress)?)?)
+        let output = self.algorithm.run_internal(update_progress)?;
+
+        Ok((self.reducer)(output, &self.input)?)
     }
 }
 
 pub trait IntoReduce<TAlgorithm: Algorithm> {
+    /// Post-processes the output of the algorithm, the reducer also receives the algorithm input
     fn reduce<TReducer, TResult>(self, reducer: TReducer) -> Reduce<TAlgorithm, TReducer>
     where
-        TReducer: Fn(TAlgorithm::Output) -> Result<TResult>;
+        TReducer: Fn(TAlgorithm::Output, &TAlgorithm::Input) -> Result<TResult>;
 }
 
-impl<TAlgorithm: Algorithm + Sync + Send> IntoReduce<Repeat<TAlgorithm>> for Repeat<TAlgorithm> {
-    fn reduce<TReducer, TResult>(self, reducer: TReducer) -> Reduce<Repeat<TAlgorithm>, TReducer>
+impl<TAlgorithm: Algorithm> IntoReduce<TAlgorithm> for TAlgorithm {
+    fn reduce<TReducer, TResult>(self, reducer: TReducer) -> Reduce<TAlgorithm, TReducer>
     where
-        TReducer: Fn(<Repeat<TAlgorithm> as Algorithm>::Output) -> Result<TResult>,
+        TReducer: Fn(TAlgorithm::Output, &TAlgorithm::Input) -> Result<TResult>,
     {
         Reduce {
+            input: self.input(),
             algorithm: self,
             reducer,
         }
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use clap::{Args, Command, FromArgMatches};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::CliError;
+
+use super::dyn_algorithm::DynAlgorithm;
+
+/// Command parameters by field name, parameters missing from it keep their defaults
+pub type Parameters = Map<String, Value>;
+
+type Factory = Box<dyn Fn(&Parameters) -> Result<(Box<dyn DynAlgorithm>, Value)> + Sync + Send>;
+
+/// Algorithms selectable by name at runtime, each built on demand from its parameters
+#[derive(Default)]
+pub struct Registry {
+    entries: BTreeMap<String, (String, Factory)>,
+}
+
+impl Registry {
+    /// Registers an algorithm built by `build` from the arguments of a command
+    pub fn register<TArguments, TAlgorithm, TBuild>(
+        &mut self,
+        name: &str,
+        description: &str,
+        build: TBuild,
+    ) where
+        TArguments: Args + FromArgMatches + Serialize + DeserializeOwned,
+        TAlgorithm: DynAlgorithm + 'static,
+        TBuild: Fn(&TArguments) -> Result<TAlgorithm> + Sync + Send + 'static,
+    {
+        let factory = move |parameters: &Parameters| {
+            let arguments = arguments::<TArguments>(parameters)?;
+            let algorithm: Box<dyn DynAlgorithm> = Box::new(build(&arguments)?);
+
+            Ok((algorithm, serde_json::to_value(&arguments)?))
+        };
+
+        self.entries
+            .insert(name.into(), (description.into(), Box::new(factory)));
+    }
+
+    /// Names and descriptions of every registered algorithm in alphabetical order
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(name, (description, _))| (name.as_str(), description.as_str()))
+    }
+
+    /// Builds the algorithm registered as `name` and returns it with the whole set of arguments
+    /// it was built from
+    pub fn create(
+        &self,
+        name: &str,
+        parameters: &Parameters,
+    ) -> Result<(Box<dyn DynAlgorithm>, Value)> {
+        match self.entries.get(name) {
+            Some((_, factory)) => factory(parameters),
+            None => Err(CliError::invalid_parameter(
+                "name",
+                name,
+                &format!(
+                    "is not a registered algorithm, available: {}",
+                    self.entries
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            )
+            .into()),
+        }
+    }
+}
+
+/// Command arguments holding the defaults declared on their clap attributes overridden by `parameters`
+fn arguments<TArguments>(parameters: &Parameters) -> Result<TArguments>
+where
+    TArguments: Args + FromArgMatches + Serialize + DeserializeOwned,
+{
+    let defaults = default_arguments::<TArguments>()?;
+
+    if parameters.is_empty() {
+        return Ok(defaults);
+    }
+
+    let mut arguments = match serde_json::to_value(&defaults)? {
+        Value::Object(arguments) => arguments,
+        _ => Map::new(),
+    };
+
+    for (name, value) in parameters {
+        if !arguments.contains_key(name) {
+            return Err(CliError::invalid_parameter(
+                name,
+                value,
+                &format!(
+                    "is not a parameter of this algorithm, available: {}",
+                    arguments
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            )
+            .into());
+        }
+
+        arguments.insert(name.clone(), value.clone());
+    }
+
+    serde_json::from_value(Value::Object(arguments)).map_err(|error| {
+        CliError::invalid_parameter(
+            "parameters",
+            Value::Object(parameters.clone()),
+            &error.to_string(),
+        )
+        .into()
+    })
+}
+
+fn default_arguments<TArguments: Args + FromArgMatches>() -> Result<TArguments> {
+    let matches =
+        TArguments::augment_args(Command::new("defaults")).try_get_matches_from(["defaults"])?;
+
+    Ok(TArguments::from_arg_matches(&matches)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Args, Serialize, Deserialize)]
+    struct TestArguments {
+        #[clap(short, long, default_value = "10")]
+        count: usize,
+        #[clap(long, default_value = "0.5")]
+        epsilon: f64,
+    }
+
+    fn parameters(value: Value) -> Parameters {
+        match value {
+            Value::Object(parameters) => parameters,
+            _ => Parameters::new(),
+        }
+    }
+
+    fn is_invalid_parameter(error: &anyhow::Error) -> bool {
+        matches!(
+            error.downcast_ref::<CliError>(),
+            Some(CliError::InvalidParameter { .. })
+        )
+    }
+
+    #[test]
+    fn missing_parameters_keep_their_defaults() {
+        let defaults = arguments::<TestArguments>(&Parameters::new()).unwrap();
+        let overridden = arguments::<TestArguments>(&parameters(json!({ "count": 3 }))).unwrap();
+
+        assert_eq!((defaults.count, defaults.epsilon), (10, 0.5));
+        assert_eq!((overridden.count, overridden.epsilon), (3, 0.5));
+    }
+
+    #[test]
+    fn unknown_and_mistyped_parameters_are_rejected() {
+        for value in [json!({ "counts": 3 }), json!({ "count": "three" })] {
+            let error = arguments::<TestArguments>(&parameters(value)).unwrap_err();
+
+            assert!(is_invalid_parameter(&error), "{}", error);
+        }
+    }
+
+    #[test]
+    fn unknown_algorithms_are_rejected() {
+        let error = Registry::default()
+            .create("missing", &Parameters::new())
+            .err()
+            .unwrap();
+
+        assert!(is_invalid_parameter(&error), "{}", error);
+    }
+}
@@ -1,11 +1,34 @@
+use std::{
+    iter,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
 use anyhow::Result;
+use log::{info, warn};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use super::algorithm::Algorithm;
+use super::{
+    algorithm::Algorithm,
+    execution::ExecutionPolicy,
+    progress::ProgressLevel,
+    random,
+    stopping::{self, EarlyStopping, RunningRepetitions, Stopped},
+    tolerate_failures::TolerateFailures,
+};
 
-pub struct Repeat<TAlgorithm> {
+pub struct Repeat<TAlgorithm: Algorithm> {
     algorithm: TAlgorithm,
     repetition_count: usize,
+    latencies: Mutex<Vec<Duration>>,
+    progress: Mutex<Option<Arc<ProgressLevel>>>,
+    early_stopping: Option<EarlyStopping<TAlgorithm::Output>>,
+    execution: Option<ExecutionPolicy>,
+    outermost: AtomicBool,
+    partial: AtomicBool,
 }
 
 impl<TAlgorithm: Algorithm> Repeat<TAlgorithm> {
@@ -13,8 +36,137 @@ impl<TAlgorithm: Algorithm> Repeat<TAlgorithm> {
         Self {
             algorithm,
             repetition_count,
+            latencies: Mutex::new(Vec::new()),
+            progress: Mutex::new(None),
+            early_stopping: None,
+            execution: None,
+            outermost: AtomicBool::new(false),
+            partial: AtomicBool::new(false),
         }
     }
+
+    /// Stops scheduling repetitions once the 95% confidence interval of the rate of
+    /// repetitions satisfying `success` is narrower than `target_width`, `None` disables it
+    pub fn stop_early<S>(mut self, target_width: Option<f64>, success: S) -> Self
+    where
+        S: Fn(&TAlgorithm::Output) -> bool + Sync + Send + 'static,
+    {
+        self.early_stopping =
+            target_width.map(|target_width| EarlyStopping::new(success, target_width));
+        self
+    }
+
+    /// Overrides the global execution policy for this repetition only
+    pub fn with_execution(mut self, policy: ExecutionPolicy) -> Self {
+        self.execution = Some(policy);
+        self
+    }
+
+    /// Keeps the outputs of the successful repetitions and summarizes the failed ones
+    /// instead of failing on the first error, `false` keeps failing on the first error
+    pub fn tolerate_failures(self, tolerate: bool) -> TolerateFailures<TAlgorithm> {
+        TolerateFailures::new(self, tolerate)
+    }
+}
+
+impl<TAlgorithm: Algorithm + Sync + Send> Repeat<TAlgorithm> {
+    /// Runs every repetition that is started before a stop and returns their results,
+    /// repetitions whose inner repetitions were stopped midway are dropped as a whole
+    pub(super) fn run_repetitions<F: Fn() + Sync + Send>(
+        &self,
+        update_progress: F,
+    ) -> Result<Vec<Result<TAlgorithm::Output>>> {
+        let mut series = Vec::with_capacity(self.repetition_count);
+        let mut latencies = Vec::with_capacity(self.repetition_count);
+        let progress = self
+            .progress
+            .lock()
+            .ok()
+            .and_then(|progress| progress.clone());
+        let target_reached = AtomicBool::new(false);
+        let _running = RunningRepetitions::enter();
+        // drawn upfront so every repetition gets the same seed whichever thread runs it
+        let seeds = random::repetition_seeds(self.repetition_count);
+
+        if let Some(early_stopping) = &self.early_stopping {
+            early_stopping.reset();
+        }
+
+        let run_repetition = |index: usize| {
+            if target_reached.load(Ordering::Relaxed) || stopping::is_stopped() {
+                return None;
+            }
+
+            let start = Instant::now();
+            let seed = seeds.as_ref().map(|seeds| seeds[index]);
+            let item = random::scoped(seed, || self.algorithm.run_internal(&update_progress));
+
+            if let Some(progress) = &progress {
+                progress.inc();
+            }
+
+            if let (Ok(item), Some(early_stopping)) = (&item, &self.early_stopping) {
+                if early_stopping.record(item) {
+                    target_reached.store(true, Ordering::Relaxed);
+                }
+            }
+
+            Some((item, start.elapsed()))
+        };
+
+        let parallel =
+            ExecutionPolicy::is_parallel(self.execution, self.outermost.load(Ordering::Relaxed));
+        let results = if parallel {
+            (0..self.repetition_count)
+                .into_par_iter()
+                .map(run_repetition)
+                .collect::<Vec<Option<(Result<TAlgorithm::Output>, Duration)>>>()
+        } else {
+            (0..self.repetition_count)
+                .map(run_repetition)
+                .collect::<Vec<Option<(Result<TAlgorithm::Output>, Duration)>>>()
+        };
+
+        for (item, latency) in results.into_iter().flatten() {
+            if matches!(&item, Err(error) if error.is::<Stopped>()) {
+                continue;
+            }
+
+            series.push(item);
+            latencies.push(latency);
+        }
+
+        let stopped =
+            series.len() < self.repetition_count && !target_reached.load(Ordering::Relaxed);
+
+        if stopped && (series.is_empty() || !self.outermost.load(Ordering::Relaxed)) {
+            return Err(Stopped.into());
+        }
+
+        if stopped {
+            warn!(
+                "{} stopped, reducing over the completed repetitions [completed={}/{}]",
+                self.name(),
+                series.len(),
+                self.repetition_count
+            );
+        } else if series.len() < self.repetition_count {
+            info!(
+                "{} reached the target confidence interval width [completed={}/{}]",
+                self.name(),
+                series.len(),
+                self.repetition_count
+            );
+        }
+
+        self.partial.store(stopped, Ordering::Relaxed);
+
+        if let Ok(mut stored) = self.latencies.lock() {
+            *stored = latencies;
+        }
+
+        Ok(series)
+    }
 }
 
 impl<TAlgorithm: Algorithm + Sync + Send> Algorithm for Repeat<TAlgorithm> {
@@ -34,24 +186,43 @@ impl<TAlgorithm: Algorithm + Sync + Send> Algorithm for Repeat<TAlgorithm> {
         self.algorithm.get_repetitions() * self.repetition_count as u64
     }
 
-    fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
-        let mut series = Vec::with_capacity(self.repetition_count);
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        iter::once((self.algorithm.name(), self.repetition_count as u64))
+            .chain(self.algorithm.progress_levels())
+            .collect()
+    }
+
+    fn attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        if let Some((level, inner_levels)) = levels.split_first() {
+            if let Ok(mut progress) = self.progress.lock() {
+                *progress = Some(level.clone());
+            }
 
-        for item in (0..self.repetition_count)
-            .into_par_iter()
-            .map(move |_| {
-                self.algorithm.run_internal(|| update_progress())
-            })
-            .collect::<Vec<Result<TAlgorithm::Output>>>()
-        {
-            series.push(item?);
+            self.algorithm.attach_progress(inner_levels);
         }
+    }
 
-        Ok(series)
+    fn mark_outermost(&self) {
+        self.outermost.store(true, Ordering::Relaxed);
+    }
+
+    fn is_partial(&self) -> bool {
+        self.partial.load(Ordering::Relaxed)
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        self.latencies
+            .lock()
+            .map(|latencies| latencies.clone())
+            .unwrap_or_default()
+    }
+
+    fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
+        self.run_repetitions(update_progress)?.into_iter().collect()
     }
 }
 
-pub trait IntoRepeat<TAlgorithm> {
+pub trait IntoRepeat<TAlgorithm: Algorithm> {
     fn repeat(self, repetition_count: usize) -> Repeat<TAlgorithm>;
 }
 
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::random;
+
+/// Timing and throughput of a single algorithm run, durations are in seconds
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunReport {
+    pub algorithm: String,
+    /// Command line parameters the run was invoked with
+    pub parameters: serde_json::Value,
+    /// Seed of the random generators, `None` when the run is not reproducible
+    pub seed: Option<u64>,
+    pub result: serde_json::Value,
+    /// Whether the run was stopped by Ctrl-C or the time budget and reduced over fewer repetitions
+    pub partial: bool,
+    pub wall_time: f64,
+    pub cpu_time: f64,
+    pub threads: usize,
+    pub repetitions: usize,
+    /// Repetitions per second of wall-clock time
+    pub throughput: f64,
+    pub latency: LatencyDistribution,
+    /// Path the report will be written to unless the command line names another one
+    #[serde(skip)]
+    pub destination: Option<String>,
+}
+
+impl RunReport {
+    /// `latencies` holds the duration of every repetition, a run without repetitions is a single one
+    pub fn new<TResult: Serialize>(
+        algorithm: String,
+        result: &TResult,
+        partial: bool,
+        wall_time: Duration,
+        cpu_time: Duration,
+        latencies: Vec<Duration>,
+    ) -> Result<Self> {
+        let latencies = if latencies.is_empty() {
+            vec![wall_time]
+        } else {
+            latencies
+        };
+
+        Ok(RunReport {
+            algorithm,
+            parameters: serde_json::Value::Null,
+            seed: random::seed(),
+            result: serde_json::to_value(result)?,
+            partial,
+            wall_time: wall_time.as_secs_f64(),
+            cpu_time: cpu_time.as_secs_f64(),
+            threads: rayon::current_num_threads(),
+            repetitions: latencies.len(),
+            throughput: latencies.len() as f64 / wall_time.as_secs_f64().max(f64::EPSILON),
+            latency: LatencyDistribution::new(latencies),
+            destination: None,
+        })
+    }
+
+    /// Number stored under `name` at any depth of the parameters
+    pub fn parameter(&self, name: &str) -> Option<f64> {
+        find_number(&self.parameters, name)
+    }
+}
+
+fn find_number(value: &serde_json::Value, name: &str) -> Option<f64> {
+    match value {
+        serde_json::Value::Object(object) => object
+            .get(name)
+            .and_then(serde_json::Value::as_f64)
+            .or_else(|| object.values().find_map(|value| find_number(value, name))),
+        _ => None,
+    }
+}
+
+/// Per-repetition latency in seconds
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LatencyDistribution {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl LatencyDistribution {
+    fn new(latencies: Vec<Duration>) -> Self {
+        let mut seconds = latencies
+            .iter()
+            .map(|latency| latency.as_secs_f64())
+            .collect::<Vec<f64>>();
+        seconds.sort_by(|a, b| a.total_cmp(b));
+
+        let quantile = |q: f64| seconds[((seconds.len() - 1) as f64 * q).round() as usize];
+
+        LatencyDistribution {
+            min: quantile(0.0),
+            mean: seconds.iter().sum::<f64>() / seconds.len() as f64,
+            p50: quantile(0.5),
+            p90: quantile(0.9),
+            p99: quantile(0.99),
+            max: quantile(1.0),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    process,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use log::warn;
+use thiserror::Error;
+
+use crate::error::CliError;
+
+/// z value of the 95% confidence interval used by [`EarlyStopping`] and [`wilson_interval`]
+const CONFIDENCE_Z: f64 = 1.96;
+/// Repetitions completed before the width is checked, the interval of fewer is unreliable
+const MIN_COMPLETED: usize = 30;
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static RUNNING_REPETITIONS: AtomicUsize = AtomicUsize::new(0);
+static DEADLINE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Error of a repetition that was stopped by Ctrl-C or the time budget before completing
+#[derive(Debug, Error)]
+#[error("run was stopped before any repetition completed")]
+pub struct Stopped;
+
+/// Marks repetitions as running while alive, only then does Ctrl-C wait for them to finish
+pub struct RunningRepetitions;
+
+impl RunningRepetitions {
+    pub fn enter() -> Self {
+        RUNNING_REPETITIONS.fetch_add(1, Ordering::Relaxed);
+        RunningRepetitions
+    }
+}
+
+impl Drop for RunningRepetitions {
+    fn drop(&mut self) {
+        RUNNING_REPETITIONS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Stops scheduling new repetitions on Ctrl-C and once `time_budget` elapsed, a second
+/// Ctrl-C or one while no repetitions are running exits immediately
+pub fn install(time_budget: Option<Duration>) -> Result<()> {
+    if let Some(time_budget) = time_budget {
+        if let Ok(mut deadline) = DEADLINE.lock() {
+            *deadline = Some(Instant::now() + time_budget);
+        }
+    }
+
+    ctrlc::set_handler(|| {
+        if RUNNING_REPETITIONS.load(Ordering::Relaxed) == 0
+            || INTERRUPTED.swap(true, Ordering::Relaxed)
+        {
+            process::exit(INTERRUPTED_EXIT_CODE);
+        }
+
+        warn!("interrupted, finishing running repetitions (press Ctrl-C again to exit)");
+    })?;
+
+    Ok(())
+}
+
+/// Whether new repetitions should no longer be scheduled
+pub fn is_stopped() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+        || DEADLINE
+            .lock()
+            .ok()
+            .and_then(|deadline| *deadline)
+            .is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Checks that an early stopping target width is a width of a rate, between 0 and 1
+pub fn validate_target_width(target_width: Option<f64>) -> Result<()> {
+    match target_width {
+        Some(width) if !(width > 0.0 && width <= 1.0) => Err(CliError::invalid_parameter(
+            "target_width",
+            width,
+            "must be between 0 and 1",
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Stops a repetition once at least [`MIN_COMPLETED`] repetitions completed and the confidence
+/// interval of its success rate is narrower than `target_width`
+pub struct EarlyStopping<TOutput> {
+    success: Box<dyn Fn(&TOutput) -> bool + Sync + Send>,
+    target_width: f64,
+    counts: Mutex<(usize, usize)>,
+}
+
+impl<TOutput> EarlyStopping<TOutput> {
+    pub fn new<S: Fn(&TOutput) -> bool + Sync + Send + 'static>(
+        success: S,
+        target_width: f64,
+    ) -> Self {
+        EarlyStopping {
+            success: Box::new(success),
+            target_width,
+            counts: Mutex::new((0, 0)),
+        }
+    }
+
+    pub fn reset(&self) {
+        if let Ok(mut counts) = self.counts.lock() {
+            *counts = (0, 0);
+        }
+    }
+
+    /// Records a completed repetition and returns whether the target width was reached
+    pub fn record(&self, output: &TOutput) -> bool {
+        let success = (self.success)(output);
+
+        match self.counts.lock() {
+            Ok(mut counts) => {
+                let (successes, completed) = &mut *counts;
+
+                *successes += success as usize;
+                *completed += 1;
+
+                if *completed < MIN_COMPLETED {
+                    return false;
+                }
+
+                let (low, high) = wilson_interval(*successes, *completed);
+
+                high - low < self.target_width
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Bounds of the 95% Wilson score interval of a success rate
+pub fn wilson_interval(successes: usize, completed: usize) -> (f64, f64) {
+    let n = completed as f64;
+    let p = successes as f64 / n;
+    let z2 = CONFIDENCE_Z.powi(2);
+
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half_width =
+        CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+
+    (center - half_width, center + half_width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wilson_interval_matches_known_bounds() {
+        let (low, high) = wilson_interval(5, 10);
+
+        assert!((low - 0.2366).abs() < 1e-4);
+        assert!((high - 0.7634).abs() < 1e-4);
+
+        let (low, high) = wilson_interval(0, 10);
+
+        assert!(low.abs() < 1e-12);
+        assert!((high - 0.2775).abs() < 1e-4);
+    }
+
+    #[test]
+    fn wilson_interval_contains_the_rate_and_narrows() {
+        let mut previous_width = f64::INFINITY;
+
+        for completed in [10, 100, 1000, 10000] {
+            let successes = completed * 3 / 10;
+            let (low, high) = wilson_interval(successes, completed);
+
+            assert!((0.0..0.3).contains(&low));
+            assert!(high > 0.3 && high <= 1.0);
+            assert!(high - low < previous_width);
+
+            previous_width = high - low;
+        }
+    }
+
+    #[test]
+    fn early_stopping_waits_for_the_minimum_completed_repetitions() {
+        // any interval is narrower than 1, so only the minimum count holds the stop back
+        let early_stopping = EarlyStopping::new(|success: &bool| *success, 1.0);
+
+        for _ in 1..MIN_COMPLETED {
+            assert!(!early_stopping.record(&true));
+        }
+
+        assert!(early_stopping.record(&true));
+
+        early_stopping.reset();
+
+        assert!(!early_stopping.record(&true));
+    }
+
+    #[test]
+    fn early_stopping_continues_until_the_interval_is_narrow() {
+        let early_stopping = EarlyStopping::new(|success: &bool| *success, 0.2);
+        let mut completed = 0;
+
+        while !early_stopping.record(&(completed % 2 == 0)) {
+            completed += 1;
+        }
+
+        let (low, high) = wilson_interval(completed / 2 + 1, completed + 1);
+
+        assert!(completed + 1 > MIN_COMPLETED);
+        assert!(high - low < 0.2);
+    }
+
+    #[test]
+    fn target_width_must_be_a_rate_width() {
+        assert!(validate_target_width(None).is_ok());
+        assert!(validate_target_width(Some(0.1)).is_ok());
+        assert!(validate_target_width(Some(0.0)).is_err());
+        assert!(validate_target_width(Some(1.5)).is_err());
+        assert!(validate_target_width(Some(f64::NAN)).is_err());
+    }
+}
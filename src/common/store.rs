@@ -0,0 +1,228 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, ErrorKind, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Error, Result};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CliError;
+
+use super::run_report::RunReport;
+
+/// Crate version followed by the commit the binary was built from
+pub const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("GIT_VERSION"));
+
+/// Run report along with when and how the run was invoked, a single line of a [`ResultStore`]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// UTC time the run finished at in RFC 3339 format
+    pub timestamp: String,
+    pub version: String,
+    /// Command line arguments the run was invoked with
+    pub command: String,
+    #[serde(flatten)]
+    pub report: RunReport,
+}
+
+impl RunRecord {
+    pub fn new(command: String, report: RunReport) -> Self {
+        RunRecord {
+            timestamp: utc_timestamp(SystemTime::now()),
+            version: VERSION.into(),
+            command,
+            report,
+        }
+    }
+}
+
+/// Append-only JSON-lines file of run records, the id of a record is its line number
+pub struct ResultStore {
+    path: String,
+}
+
+impl ResultStore {
+    pub fn new(path: &str) -> Self {
+        ResultStore { path: path.into() }
+    }
+
+    pub fn append(&self, record: &RunRecord) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("cannot open results store [path={}]", self.path))?;
+
+        // a single write keeps concurrent runs from interleaving their lines
+        file.write_all((serde_json::to_string(record)? + "\n").as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Every record with its id from the oldest to the latest, a missing store holds no records
+    /// and malformed lines (e.g. cut short by a crash) are skipped
+    pub fn records(&self) -> Result<Vec<(usize, RunRecord)>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => {
+                return Err(Error::new(error)
+                    .context(format!("cannot open results store [path={}]", self.path)))
+            }
+        };
+
+        let mut records = Vec::new();
+
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let record = match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => serde_json::from_str::<RunRecord>(&line).map_err(Error::new),
+                Err(error) => Err(Error::new(error)),
+            };
+
+            match record {
+                Ok(record) => records.push((index + 1, record)),
+                Err(error) => warn!(
+                    "skipping malformed results store record [path={} id={} error={}]",
+                    self.path,
+                    index + 1,
+                    error
+                ),
+            }
+        }
+
+        Ok(records)
+    }
+
+    pub fn record(&self, id: usize) -> Result<RunRecord> {
+        self.records()?
+            .into_iter()
+            .find(|(record_id, _)| *record_id == id)
+            .map(|(_, record)| record)
+            .ok_or_else(|| {
+                CliError::invalid_parameter("id", id, &format!("is not in {}", self.path)).into()
+            })
+    }
+}
+
+/// `time` formatted as `YYYY-MM-DDThh:mm:ssZ`
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+
+    // civil date of a day count since 1970-01-01 (Howard Hinnant's days_from_civil inverse)
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process, time::Duration};
+
+    use super::*;
+
+    fn store(name: &str) -> ResultStore {
+        let path = std::env::temp_dir().join(format!("store-{}-{}.jsonl", name, process::id()));
+        let _ = fs::remove_file(&path);
+
+        ResultStore::new(path.to_str().unwrap())
+    }
+
+    fn record(algorithm: &str) -> RunRecord {
+        let time = Duration::from_millis(10);
+        let report = RunReport::new(algorithm.into(), &42, false, time, time, Vec::new()).unwrap();
+
+        RunRecord::new(format!("{} --repeat-count 1", algorithm), report)
+    }
+
+    #[test]
+    fn missing_store_holds_no_records() {
+        assert!(store("missing").records().unwrap().is_empty());
+    }
+
+    #[test]
+    fn appended_records_are_read_back_by_line_number() {
+        let store = store("append");
+
+        store.append(&record("first")).unwrap();
+        store.append(&record("second")).unwrap();
+
+        let records = store.records().unwrap();
+        let second = store.record(2).unwrap();
+
+        assert_eq!(
+            records
+                .iter()
+                .map(|(id, record)| (*id, record.report.algorithm.as_str()))
+                .collect::<Vec<(usize, &str)>>(),
+            [(1, "first"), (2, "second")]
+        );
+        assert_eq!(second.command, "second --repeat-count 1");
+        assert_eq!(second.report.result, 42);
+        assert!(store.record(3).is_err());
+
+        fs::remove_file(&store.path).unwrap();
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped_and_keep_their_ids() {
+        let store = store("malformed");
+
+        store.append(&record("first")).unwrap();
+        OpenOptions::new()
+            .append(true)
+            .open(&store.path)
+            .unwrap()
+            .write_all(b"{\"timestamp\": \"cut short\n\nnot json\n")
+            .unwrap();
+        store.append(&record("last")).unwrap();
+
+        let ids = store
+            .records()
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<usize>>();
+
+        assert_eq!(ids, [1, 5]);
+        assert!(store.record(2).is_err());
+
+        fs::remove_file(&store.path).unwrap();
+    }
+
+    #[test]
+    fn timestamps_are_utc_civil_dates() {
+        let time = |seconds| UNIX_EPOCH + Duration::from_secs(seconds);
+
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_timestamp(time(951_827_696)), "2000-02-29T12:34:56Z");
+        assert_eq!(utc_timestamp(time(4_107_542_399)), "2100-02-28T23:59:59Z");
+    }
+}
@@ -0,0 +1,63 @@
+use serde_json::Value;
+
+/// Aligned text table, every column is as wide as its widest cell
+pub fn format_table(header: &[String], lines: &[Vec<String>]) -> String {
+    let widths = (0..header.len())
+        .map(|column| {
+            lines
+                .iter()
+                .map(|cells| cells[column].len())
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<usize>>();
+
+    [header.to_vec()]
+        .iter()
+        .chain(lines.iter())
+        .map(|cells| {
+            cells
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Cell of a JSON value, floating point numbers are rounded and missing values are shown as `-`
+pub fn format_cell(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::Number(number)) => match number.as_f64() {
+            Some(value) if number.is_f64() => format!("{:.4}", value),
+            _ => number.to_string(),
+        },
+        Some(Value::String(value)) => value.clone(),
+        Some(value) => value.to_string(),
+        None => "-".into(),
+    }
+}
+
+/// Leaf values of nested objects keyed by their dotted path
+pub fn fields(prefix: &str, value: &Value) -> Vec<(String, Value)> {
+    match value {
+        Value::Object(object) => object
+            .iter()
+            .flat_map(|(key, value)| {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+
+                fields(&path, value)
+            })
+            .collect(),
+        value => vec![(prefix.to_string(), value.clone())],
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use anyhow::{Error, Result};
+use log::warn;
+use serde::Serialize;
+
+use super::{algorithm::Algorithm, progress::ProgressLevel, repeat::Repeat};
+
+/// Maximal amount of failure messages kept by [`Failures`]
+const FAILURE_SAMPLE_LIMIT: usize = 5;
+
+/// Outputs of the successful repetitions along with a summary of the failed ones
+#[derive(Debug)]
+pub struct Repetitions<TOutput> {
+    pub outputs: Vec<TOutput>,
+    pub failures: Failures,
+    first_error: Option<Error>,
+}
+
+impl<TOutput> Repetitions<TOutput> {
+    /// Fails with the first error when no repetition succeeded, keeping its kind
+    pub fn require_outputs(&mut self) -> Result<()> {
+        match self.first_error.take() {
+            Some(error) if self.outputs.is_empty() => Err(error.context(format!(
+                "every repetition failed [failures={}]",
+                self.failures.count
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Failures {
+    pub count: usize,
+    /// Messages of the first failures
+    pub samples: Vec<String>,
+}
+
+impl Failures {
+    fn record(&mut self, error: &Error) {
+        self.count += 1;
+
+        if self.samples.len() < FAILURE_SAMPLE_LIMIT {
+            self.samples.push(format!("{:#}", error));
+        }
+    }
+}
+
+pub struct TolerateFailures<TAlgorithm: Algorithm> {
+    repeat: Repeat<TAlgorithm>,
+    tolerate: bool,
+}
+
+impl<TAlgorithm: Algorithm> TolerateFailures<TAlgorithm> {
+    pub(super) fn new(repeat: Repeat<TAlgorithm>, tolerate: bool) -> Self {
+        TolerateFailures { repeat, tolerate }
+    }
+}
+
+impl<TAlgorithm: Algorithm + Sync + Send> Algorithm for TolerateFailures<TAlgorithm> {
+    type Input = <Repeat<TAlgorithm> as Algorithm>::Input;
+    type Output = Repetitions<TAlgorithm::Output>;
+
+    fn name(&self) -> String {
+        self.repeat.name()
+    }
+
+    fn input(&self) -> Self::Input {
+        self.repeat.input()
+    }
+
+    fn get_repetitions(&self) -> u64 {
+        self.repeat.get_repetitions()
+    }
+
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        self.repeat.progress_levels()
+    }
+
+    fn attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        self.repeat.attach_progress(levels)
+    }
+
+    fn mark_outermost(&self) {
+        self.repeat.mark_outermost()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.repeat.is_partial()
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        self.repeat.latencies()
+    }
+
+    fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
+        let mut outputs = Vec::new();
+        let mut failures = Failures::default();
+        let mut first_error = None;
+
+        for item in self.repeat.run_repetitions(update_progress)? {
+            match item {
+                Ok(output) => outputs.push(output),
+                Err(error) if !self.tolerate => return Err(error),
+                Err(error) => {
+                    failures.record(&error);
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+
+        if failures.count > 0 {
+            warn!(
+                "{} tolerated failures [failures={}/{} first={:?}]",
+                self.name(),
+                failures.count,
+                failures.count + outputs.len(),
+                failures.samples.first()
+            );
+        }
+
+        Ok(Repetitions {
+            outputs,
+            failures,
+            first_error,
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use anyhow::Result;
+
+use crate::error::CliError;
+
+/// Checks that an amount of repetitions or generated items is at least 1
+pub fn validate_count(name: &str, count: usize) -> Result<()> {
+    if count == 0 {
+        Err(CliError::invalid_parameter(name, count, "must be at least 1").into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Range of the generated item values, fails when `min_value` is above `max_value`
+pub fn value_range<TValue: PartialOrd + Display>(
+    min_value: TValue,
+    max_value: TValue,
+) -> Result<RangeInclusive<TValue>> {
+    if min_value > max_value {
+        Err(CliError::invalid_parameter(
+            "max_value",
+            max_value,
+            &format!("must not be below min_value={}", min_value),
+        )
+        .into())
+    } else {
+        Ok(min_value..=max_value)
+    }
+}
@@ -1,4 +1,6 @@
-use super::algorithm::Algorithm;
+use std::{sync::Arc, time::Duration};
+
+use super::{algorithm::Algorithm, progress::ProgressLevel};
 
 pub struct WithName<TAlgorithm> {
     algorithm: TAlgorithm,
@@ -21,6 +23,26 @@ impl<TAlgorithm: Algorithm> Algorithm for WithName<TAlgorithm> {
         self.algorithm.get_repetitions()
     }
 
+    fn progress_levels(&self) -> Vec<(String, u64)> {
+        self.algorithm.progress_levels()
+    }
+
+    fn attach_progress(&self, levels: &[Arc<ProgressLevel>]) {
+        self.algorithm.attach_progress(levels)
+    }
+
+    fn mark_outermost(&self) {
+        self.algorithm.mark_outermost()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.algorithm.is_partial()
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        self.algorithm.latencies()
+    }
+
     fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> anyhow::Result<Self::Output> {
         self.algorithm.run_internal(update_progress)
     }
@@ -0,0 +1,93 @@
+use std::{fmt::Debug, time::Duration};
+
+use anyhow::Result;
+
+use super::{algorithm::Algorithm, execution::ExecutionPolicy, random};
+
+/// Runs two algorithms built from the same input side by side and pairs their outputs,
+/// both report their progress through a single level since side by side levels cannot nest
+pub struct Zip<TInput, TLeft, TRight> {
+    input: TInput,
+    left: TLeft,
+    right: TRight,
+}
+
+impl<TInput, TLeft, TRight> Algorithm for Zip<TInput, TLeft, TRight>
+where
+    TInput: Debug + Clone + Sync + Send,
+    TLeft: Algorithm + Sync + Send,
+    TRight: Algorithm + Sync + Send,
+{
+    type Input = TInput;
+    type Output = (TLeft::Output, TRight::Output);
+
+    fn name(&self) -> String {
+        format!("{} zipped with {}", self.left.name(), self.right.name())
+    }
+
+    fn input(&self) -> Self::Input {
+        self.input.clone()
+    }
+
+    fn get_repetitions(&self) -> u64 {
+        self.left.get_repetitions() + self.right.get_repetitions()
+    }
+
+    fn mark_outermost(&self) {
+        self.left.mark_outermost();
+        self.right.mark_outermost();
+    }
+
+    fn is_partial(&self) -> bool {
+        self.left.is_partial() || self.right.is_partial()
+    }
+
+    fn latencies(&self) -> Vec<Duration> {
+        Vec::new()
+    }
+
+    fn run_internal<F: Fn() + Sync + Send>(&self, update_progress: F) -> Result<Self::Output> {
+        let seeds = random::repetition_seeds(2);
+        let seed = |index: usize| seeds.as_ref().map(|seeds| seeds[index]);
+        let left = || random::scoped(seed(0), || self.left.run_internal(&update_progress));
+        let right = || random::scoped(seed(1), || self.right.run_internal(&update_progress));
+
+        let (left, right) = if ExecutionPolicy::current() == ExecutionPolicy::Sequential {
+            (left(), right())
+        } else {
+            rayon::join(left, right)
+        };
+
+        Ok((left?, right?))
+    }
+}
+
+pub trait IntoZip: Sized {
+    /// Builds both algorithms from clones of this input and zips them
+    fn into_zip<TLeft, TRight, TLeftBuilder, TRightBuilder>(
+        self,
+        left: TLeftBuilder,
+        right: TRightBuilder,
+    ) -> Zip<Self, TLeft, TRight>
+    where
+        TLeftBuilder: FnOnce(Self) -> TLeft,
+        TRightBuilder: FnOnce(Self) -> TRight;
+}
+
+impl<TInput: Debug + Clone + Sync + Send> IntoZip for TInput {
+    fn into_zip<TLeft, TRight, TLeftBuilder, TRightBuilder>(
+        self,
+        left: TLeftBuilder,
+        right: TRightBuilder,
+    ) -> Zip<Self, TLeft, TRight>
+    where
+        TLeftBuilder: FnOnce(Self) -> TLeft,
+        TRightBuilder: FnOnce(Self) -> TRight,
+    {
+        Zip {
+            left: left(self.clone()),
+            right: right(self.clone()),
+            input: self,
+        }
+    }
+}
@@ -2,10 +2,11 @@ use std::{fs::File, io::Write};
 
 use anyhow::{Ok, Result};
 use clap::{Args, IntoApp};
+use serde::Serialize;
 
 use crate::command_line_arguments::CommandLineArguments;
 
-#[derive(Debug, Args)]
+#[derive(Debug, Args, Serialize)]
 pub struct DocumentCommand {
     #[clap(short, long)]
     path: String,
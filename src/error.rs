@@ -0,0 +1,126 @@
+use std::{io, process};
+
+use thiserror::Error;
+
+use crate::q3::matrix::MatrixError;
+
+/// Exit code table printed after the help, the tests check it against [`CliError::exit_code`]
+pub const EXIT_CODES_HELP: &str = "EXIT CODES:
+    0      success
+    2      invalid parameter
+    3      i/o failure
+    4      numeric failure
+    5      algorithm failure
+    6      invalid file format
+    130    interrupted by Ctrl-C";
+
+/// Failure kinds reported by the command line, each with its own process exit code
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("invalid parameter {name}={value}: {reason}")]
+    InvalidParameter {
+        name: String,
+        value: String,
+        reason: String,
+    },
+    #[error("i/o failure: {0}")]
+    Io(String),
+    #[error("numeric failure: {0}")]
+    Numeric(String),
+    #[error("algorithm failure: {0}")]
+    Algorithm(String),
+    #[error("invalid file format: {0}")]
+    Format(String),
+}
+
+impl CliError {
+    pub fn invalid_parameter<TValue: ToString>(name: &str, value: TValue, reason: &str) -> Self {
+        CliError::InvalidParameter {
+            name: name.into(),
+            value: value.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// 2 matches the exit code clap uses for malformed arguments
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::InvalidParameter { .. } => 2,
+            CliError::Io(_) => 3,
+            CliError::Numeric(_) => 4,
+            CliError::Algorithm(_) => 5,
+            CliError::Format(_) => 6,
+        }
+    }
+
+    /// Prints the message to stderr and terminates the process with the matching exit code
+    pub fn exit(&self) -> ! {
+        eprintln!("error: {}", self);
+
+        process::exit(self.exit_code())
+    }
+}
+
+/// Classifies the errors raised across the crate by their source type, unknown
+/// sources are reported as algorithm failures
+impl From<anyhow::Error> for CliError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<CliError>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+
+        let message = format!("{:#}", error);
+
+        if error.is::<io::Error>() {
+            CliError::Io(message)
+        } else if error.is::<MatrixError>() {
+            CliError::Numeric(message)
+        } else {
+            CliError::Algorithm(message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::stopping::INTERRUPTED_EXIT_CODE;
+
+    fn help_line(code: i32, description: &str) -> String {
+        format!("    {:<7}{}", code, description)
+    }
+
+    #[test]
+    fn exit_code_help_lists_every_error_kind() {
+        let errors = [
+            CliError::invalid_parameter("name", 1, "reason"),
+            CliError::Io(String::new()),
+            CliError::Numeric(String::new()),
+            CliError::Algorithm(String::new()),
+            CliError::Format(String::new()),
+        ];
+
+        for error in errors {
+            // the description is the message prefix of the kind
+            let message = error.to_string();
+            let description = match &error {
+                CliError::InvalidParameter { .. } => "invalid parameter",
+                _ => message.split(':').next().unwrap(),
+            };
+
+            assert!(
+                EXIT_CODES_HELP
+                    .lines()
+                    .any(|line| line == help_line(error.exit_code(), description)),
+                "{description} ({}) is missing",
+                error.exit_code()
+            );
+        }
+
+        assert!(EXIT_CODES_HELP
+            .lines()
+            .any(|line| line == help_line(INTERRUPTED_EXIT_CODE, "interrupted by Ctrl-C")));
+        assert_eq!(EXIT_CODES_HELP.lines().count(), 1 + 1 + 5 + 1);
+    }
+}
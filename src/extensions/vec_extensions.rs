@@ -4,6 +4,8 @@ use rand::{
     Rng,
 };
 
+use crate::common::random;
+
 pub trait SampleUniformVecExtensions<TItem: SampleUniform> {
     fn with_random_items_in_range<TRange: SampleRange<TItem>, F: Fn() -> TRange>(
         length: usize,
@@ -16,9 +18,9 @@ impl<TItem: SampleUniform> SampleUniformVecExtensions<TItem> for Vec<TItem> {
         length: usize,
         range: F,
     ) -> Self {
-        let mut thread_rng = rand::thread_rng();
+        let mut rng = random::rng();
 
-        (0..length).map(|_| thread_rng.gen_range(range())).collect()
+        (0..length).map(|_| rng.gen_range(range())).collect()
     }
 }
 
@@ -38,17 +40,23 @@ impl L2NormVecExtension<f64> for Vec<f64> {
     }
 }
 
-pub trait Single<TResult> {
-    fn single(self) -> Result<TResult>;
+/// Euclidean length and distance, unlike [`L2NormVecExtension::l2_norm`] which is the squared length
+pub trait EuclideanNormExtension {
+    fn euclidean_norm(&self) -> f64;
+    fn euclidean_distance(&self, other: &[f64]) -> f64;
 }
 
-impl<TItem : Copy> Single<TItem> for Vec<TItem> {
-    fn single(self) -> Result<TItem> {
-        match self.len() {
-            0 => Err(anyhow::Error::msg("Sequence contains no elements")),
-            1 => Ok(self[0]),
-            _ => Err(anyhow::Error::msg("sequence contains multiple elements"))
-        }
+impl EuclideanNormExtension for [f64] {
+    fn euclidean_norm(&self) -> f64 {
+        self.iter().map(|num| num.powi(2)).sum::<f64>().sqrt()
+    }
+
+    fn euclidean_distance(&self, other: &[f64]) -> f64 {
+        self.iter()
+            .zip(other.iter())
+            .map(|(left, right)| (left - right).powi(2))
+            .sum::<f64>()
+            .sqrt()
     }
 }
 
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::common::{
+    store::{ResultStore, RunRecord},
+    table::{fields, format_cell, format_table},
+};
+
+#[derive(Debug, Args, Serialize)]
+pub struct HistoryCommand {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+impl HistoryCommand {
+    pub fn invoke(&self, store: &ResultStore) -> Result<()> {
+        match &self.command {
+            Commands::List(command) => command.invoke(store),
+            Commands::Show(command) => command.invoke(store),
+            Commands::Diff(command) => command.invoke(store),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand, Serialize)]
+enum Commands {
+    ///Lists the recorded runs from the oldest to the latest with a column per result field
+    List(ListCommand),
+    ///Prints a recorded run as JSON
+    Show(ShowCommand),
+    ///Lists the fields that differ between two recorded runs
+    Diff(DiffCommand),
+}
+
+#[derive(Debug, Args, Serialize)]
+struct ListCommand {
+    ///Only list runs whose command line contains this text
+    #[clap(short, long)]
+    command: Option<String>,
+    ///Only list runs of algorithms whose name contains this text
+    #[clap(short, long)]
+    algorithm: Option<String>,
+    ///Only list this amount of latest runs
+    #[clap(short, long)]
+    last: Option<usize>,
+}
+
+impl ListCommand {
+    fn invoke(&self, store: &ResultStore) -> Result<()> {
+        let contains = |text: &str, filter: &Option<String>| {
+            filter.as_ref().is_none_or(|filter| text.contains(filter))
+        };
+
+        let mut records = store
+            .records()?
+            .into_iter()
+            .filter(|(_, record)| {
+                contains(&record.command, &self.command)
+                    && contains(&record.report.algorithm, &self.algorithm)
+            })
+            .collect::<Vec<(usize, RunRecord)>>();
+
+        if let Some(last) = self.last {
+            records.drain(..records.len().saturating_sub(last));
+        }
+
+        let results = records
+            .iter()
+            .map(|(_, record)| fields("", &record.report.result))
+            .collect::<Vec<Vec<(String, Value)>>>();
+
+        // arrays such as failure samples do not fit in a column
+        let mut columns = Vec::new();
+
+        for (field, value) in results.iter().flatten() {
+            if !value.is_array() && !columns.contains(field) {
+                columns.push(field.clone());
+            }
+        }
+
+        let header = ["id", "timestamp", "command", "seed", "partial", "wall_time"]
+            .into_iter()
+            .map(String::from)
+            .chain(columns.iter().cloned())
+            .collect::<Vec<String>>();
+
+        let lines = records
+            .iter()
+            .zip(results.iter())
+            .map(|((id, record), result)| {
+                let mut cells = vec![
+                    id.to_string(),
+                    record.timestamp.clone(),
+                    record.command.clone(),
+                    record
+                        .report
+                        .seed
+                        .map_or("-".into(), |seed| seed.to_string()),
+                    record.report.partial.to_string(),
+                    format!("{:.3}s", record.report.wall_time),
+                ];
+
+                cells.extend(columns.iter().map(|column| {
+                    format_cell(
+                        result
+                            .iter()
+                            .find(|(field, _)| field == column)
+                            .map(|(_, value)| value),
+                    )
+                }));
+
+                cells
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        println!("{}", format_table(&header, &lines));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args, Serialize)]
+struct ShowCommand {
+    ///Id of the recorded run as listed by history list
+    id: usize,
+}
+
+impl ShowCommand {
+    fn invoke(&self, store: &ResultStore) -> Result<()> {
+        println!("{}", serde_json::to_string_pretty(&store.record(self.id)?)?);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Args, Serialize)]
+struct DiffCommand {
+    ///Id of the run to compare from
+    from: usize,
+    ///Id of the run to compare to
+    to: usize,
+    ///List the equal fields as well
+    #[clap(long)]
+    all: bool,
+}
+
+impl DiffCommand {
+    fn invoke(&self, store: &ResultStore) -> Result<()> {
+        let from = fields("", &serde_json::to_value(store.record(self.from)?)?);
+        let to = fields("", &serde_json::to_value(store.record(self.to)?)?);
+
+        let mut names = from
+            .iter()
+            .map(|(field, _)| field.clone())
+            .collect::<Vec<String>>();
+
+        for (field, _) in &to {
+            if !names.contains(field) {
+                names.push(field.clone());
+            }
+        }
+
+        let find = |fields: &[(String, Value)], name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let lines = names
+            .iter()
+            .map(|name| (name, find(&from, name), find(&to, name)))
+            .filter(|(_, from, to)| self.all || from != to)
+            .map(|(name, from, to)| {
+                let change = match (
+                    from.as_ref().and_then(Value::as_f64),
+                    to.as_ref().and_then(Value::as_f64),
+                ) {
+                    (Some(from), Some(to)) if from != to => format!("{:+.4}", to - from),
+                    _ => String::new(),
+                };
+
+                vec![
+                    name.clone(),
+                    format_cell(from.as_ref()),
+                    format_cell(to.as_ref()),
+                    change,
+                ]
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let header = [
+            "field".to_string(),
+            format!("#{}", self.from),
+            format!("#{}", self.to),
+            "change".to_string(),
+        ];
+
+        println!("{}", format_table(&header, &lines));
+
+        Ok(())
+    }
+}
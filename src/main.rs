@@ -1,13 +1,22 @@
 use clap::StructOpt;
 use command_line_arguments::CommandLineArguments;
+use error::CliError;
 mod command_line_arguments;
 mod common;
 mod document;
+mod error;
 mod extensions;
+mod history;
+mod plot;
 mod q1;
 mod q2;
 mod q3;
+mod report;
+mod rnla;
+mod run;
 
 fn main() {
-    CommandLineArguments::parse().invoke().unwrap();
+    if let Err(error) = CommandLineArguments::parse().invoke() {
+        CliError::from(error).exit();
+    }
 }
@@ -0,0 +1,298 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+const WIDTH: f64 = 720.0;
+const HEIGHT: f64 = 450.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 30.0;
+const MARGIN_TOP: f64 = 50.0;
+const MARGIN_BOTTOM: f64 = 60.0;
+const TICK_COUNT: f64 = 6.0;
+const COLORS: [&str; 6] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd", "#8c564b",
+];
+
+enum Mark {
+    Line(Vec<(f64, f64)>),
+    /// Bars as `(start, end, height)`
+    Bars(Vec<(f64, f64, f64)>),
+    VerticalLine(f64),
+    HorizontalLine(f64),
+}
+
+struct Layer {
+    label: String,
+    mark: Mark,
+}
+
+/// Two dimensional chart rendered as a standalone SVG document, every layer gets its own
+/// color and legend entry, reference lines are dashed
+pub struct Chart {
+    title: String,
+    x_label: String,
+    y_label: String,
+    layers: Vec<Layer>,
+}
+
+impl Chart {
+    pub fn new(title: &str, x_label: &str, y_label: &str) -> Self {
+        Chart {
+            title: title.into(),
+            x_label: x_label.into(),
+            y_label: y_label.into(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Polyline through `points` with a dot on every point
+    pub fn line(self, label: &str, points: Vec<(f64, f64)>) -> Self {
+        self.layer(label, Mark::Line(points))
+    }
+
+    /// Histogram bars given as `(start, end, height)`
+    pub fn bars(self, label: &str, bars: Vec<(f64, f64, f64)>) -> Self {
+        self.layer(label, Mark::Bars(bars))
+    }
+
+    pub fn vertical_line(self, label: &str, x: f64) -> Self {
+        self.layer(label, Mark::VerticalLine(x))
+    }
+
+    pub fn horizontal_line(self, label: &str, y: f64) -> Self {
+        self.layer(label, Mark::HorizontalLine(y))
+    }
+
+    fn layer(mut self, label: &str, mark: Mark) -> Self {
+        self.layers.push(Layer {
+            label: label.into(),
+            mark,
+        });
+        self
+    }
+
+    pub fn render(&self) -> Result<String> {
+        let (x_ticks, y_ticks) = self.ticks();
+        let (x_min, x_max) = (x_ticks[0], x_ticks[x_ticks.len() - 1]);
+        let (y_min, y_max) = (y_ticks[0], y_ticks[y_ticks.len() - 1]);
+
+        let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+        let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let x = |value: f64| MARGIN_LEFT + (value - x_min) / (x_max - x_min) * plot_width;
+        let y = |value: f64| MARGIN_TOP + (y_max - value) / (y_max - y_min) * plot_height;
+
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">"#,
+            w = WIDTH,
+            h = HEIGHT
+        )?;
+        writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="white"/>"#,
+            WIDTH, HEIGHT
+        )?;
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle" font-size="16">{}</text>"#,
+            WIDTH / 2.0,
+            MARGIN_TOP / 2.0 + 5.0,
+            escape(&self.title)
+        )?;
+
+        for tick in &x_ticks {
+            writeln!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{}" x2="{x:.1}" y2="{}" stroke="#e0e0e0"/><text x="{x:.1}" y="{}" text-anchor="middle">{}</text>"##,
+                MARGIN_TOP,
+                MARGIN_TOP + plot_height,
+                MARGIN_TOP + plot_height + 18.0,
+                format_tick(*tick),
+                x = x(*tick)
+            )?;
+        }
+
+        for tick in &y_ticks {
+            writeln!(
+                svg,
+                r##"<line x1="{}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="#e0e0e0"/><text x="{}" y="{:.1}" text-anchor="end">{}</text>"##,
+                MARGIN_LEFT,
+                MARGIN_LEFT + plot_width,
+                MARGIN_LEFT - 8.0,
+                y(*tick) + 4.0,
+                format_tick(*tick),
+                y = y(*tick)
+            )?;
+        }
+
+        writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+            MARGIN_LEFT, MARGIN_TOP, plot_width, plot_height
+        )?;
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            MARGIN_LEFT + plot_width / 2.0,
+            HEIGHT - 15.0,
+            escape(&self.x_label)
+        )?;
+        writeln!(
+            svg,
+            r#"<text x="{x}" y="{y}" text-anchor="middle" transform="rotate(-90 {x} {y})">{}</text>"#,
+            escape(&self.y_label),
+            x = 20.0,
+            y = MARGIN_TOP + plot_height / 2.0
+        )?;
+
+        for (index, layer) in self.layers.iter().enumerate() {
+            let color = COLORS[index % COLORS.len()];
+
+            match &layer.mark {
+                Mark::Line(points) => {
+                    let path = points
+                        .iter()
+                        .map(|(px, py)| format!("{:.1},{:.1}", x(*px), y(*py)))
+                        .collect::<Vec<String>>()
+                        .join(" ");
+
+                    writeln!(
+                        svg,
+                        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                        path, color
+                    )?;
+
+                    for (px, py) in points {
+                        writeln!(
+                            svg,
+                            r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{}"/>"#,
+                            x(*px),
+                            y(*py),
+                            color
+                        )?;
+                    }
+                }
+                Mark::Bars(bars) => {
+                    for (start, end, height) in bars {
+                        writeln!(
+                            svg,
+                            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" fill-opacity="0.6" stroke="{}"/>"#,
+                            x(*start),
+                            y(*height),
+                            x(*end) - x(*start),
+                            y(y_min) - y(*height),
+                            color,
+                            color
+                        )?;
+                    }
+                }
+                Mark::VerticalLine(value) => {
+                    writeln!(
+                        svg,
+                        r#"<line x1="{x:.1}" y1="{}" x2="{x:.1}" y2="{}" stroke="{}" stroke-width="2" stroke-dasharray="6 4"/>"#,
+                        MARGIN_TOP,
+                        MARGIN_TOP + plot_height,
+                        color,
+                        x = x(*value)
+                    )?;
+                }
+                Mark::HorizontalLine(value) => {
+                    writeln!(
+                        svg,
+                        r#"<line x1="{}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="{}" stroke-width="2" stroke-dasharray="6 4"/>"#,
+                        MARGIN_LEFT,
+                        MARGIN_LEFT + plot_width,
+                        color,
+                        y = y(*value)
+                    )?;
+                }
+            }
+
+            let legend_y = MARGIN_TOP + 18.0 + index as f64 * 18.0;
+            let legend_x = MARGIN_LEFT + plot_width - 190.0;
+
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="14" height="4" fill="{}"/><text x="{}" y="{}">{}</text>"#,
+                legend_x,
+                legend_y - 6.0,
+                color,
+                legend_x + 20.0,
+                legend_y,
+                escape(&layer.label)
+            )?;
+        }
+
+        svg.push_str("</svg>\n");
+
+        Ok(svg)
+    }
+
+    /// Evenly spaced round ticks covering every layer on both axes
+    fn ticks(&self) -> (Vec<f64>, Vec<f64>) {
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+
+        for layer in &self.layers {
+            match &layer.mark {
+                Mark::Line(points) => {
+                    xs.extend(points.iter().map(|(x, _)| *x));
+                    ys.extend(points.iter().map(|(_, y)| *y));
+                }
+                Mark::Bars(bars) => {
+                    xs.extend(bars.iter().flat_map(|(start, end, _)| [*start, *end]));
+                    ys.extend(bars.iter().map(|(_, _, height)| *height));
+                    ys.push(0.0);
+                }
+                Mark::VerticalLine(x) => xs.push(*x),
+                Mark::HorizontalLine(y) => ys.push(*y),
+            }
+        }
+
+        (nice_ticks(&xs), nice_ticks(&ys))
+    }
+}
+
+/// Ticks at multiples of 1, 2 or 5 times a power of ten spanning `values`
+fn nice_ticks(values: &[f64]) -> Vec<f64> {
+    let finite = values.iter().copied().filter(|value| value.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+
+    let (min, max) = match (min.is_finite(), max > min) {
+        (false, _) => (0.0, 1.0),
+        (true, false) => (min - 0.5, max + 0.5),
+        (true, true) => (min, max),
+    };
+
+    let rough_step = (max - min) / TICK_COUNT;
+    let magnitude = 10_f64.powf(rough_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough_step)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).floor() as i64;
+    let last = (max / step).ceil() as i64;
+
+    (first..=last).map(|index| index as f64 * step).collect()
+}
+
+fn format_tick(value: f64) -> String {
+    let rounded = format!("{:.4}", value);
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+
+    match trimmed {
+        "-0" => "0".into(),
+        trimmed => trimmed.into(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use nameof::name_of;
+use serde::Serialize;
+
+use crate::{
+    common::validation::validate_count,
+    q2::{Q2AmplificatedCommand, Q2NaiveCommand},
+    q3::Q3Command,
+};
+
+use super::chart::Chart;
+
+#[derive(Debug, Args, Serialize)]
+pub struct HistogramCommand {
+    ///Amount of bins the estimates are counted in
+    #[clap(short, long, default_value = "30")]
+    bins: usize,
+    #[clap(subcommand)]
+    estimator: Estimator,
+}
+
+#[derive(Debug, Subcommand, Serialize)]
+enum Estimator {
+    ///Single L2 estimates without amplification
+    Q2Naive(Q2NaiveCommand),
+    ///Median of means L2 estimates
+    Q2Amplificated(Q2AmplificatedCommand),
+    ///Norms of the projected arrays
+    Q3(Q3Command),
+}
+
+impl HistogramCommand {
+    pub fn chart(&self) -> Result<Chart> {
+        validate_count(name_of!(bins in Self), self.bins)?;
+
+        let (title, (estimates, l2_norm)) = match &self.estimator {
+            Estimator::Q2Naive(command) => ("Q2 naive estimates", command.estimates()?),
+            Estimator::Q2Amplificated(command) => {
+                ("Q2 amplificated estimates", command.estimates()?)
+            }
+            Estimator::Q3(command) => ("Q3 projected norms", command.estimates()?),
+        };
+
+        let min = estimates.iter().copied().fold(l2_norm, f64::min);
+        let max = estimates.iter().copied().fold(l2_norm, f64::max);
+        let width = if max > min {
+            (max - min) / self.bins as f64
+        } else {
+            1.0
+        };
+
+        let mut counts = vec![0; self.bins];
+
+        for estimate in &estimates {
+            let bin = ((estimate - min) / width) as usize;
+            counts[bin.min(self.bins - 1)] += 1;
+        }
+
+        let bars = counts
+            .iter()
+            .enumerate()
+            .map(|(bin, count)| {
+                let start = min + bin as f64 * width;
+
+                (start, start + width, *count as f64)
+            })
+            .collect();
+
+        Ok(Chart::new(title, "estimate", "count")
+            .bars("estimates", bars)
+            .vertical_line("L2 norm", l2_norm))
+    }
+}
@@ -0,0 +1,56 @@
+mod chart;
+mod histogram;
+mod secretary;
+mod success_rate;
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use log::info;
+use serde::Serialize;
+
+use crate::common::store::ResultStore;
+
+use self::histogram::HistogramCommand;
+
+pub use self::{
+    secretary::SecretaryCommand,
+    success_rate::{Question, SuccessRateCommand},
+};
+
+#[derive(Debug, Args, Serialize)]
+pub struct PlotCommand {
+    ///Path the SVG chart will be written to
+    #[clap(short, long, default_value = "plot.svg")]
+    path: String,
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+impl PlotCommand {
+    pub fn invoke(&self, store: &ResultStore) -> Result<()> {
+        let chart = match &self.command {
+            Commands::SuccessRate(command) => command.chart(store)?,
+            Commands::Histogram(command) => command.chart()?,
+            Commands::Secretary(command) => command.chart()?,
+        };
+
+        fs::write(&self.path, chart.render()?)
+            .with_context(|| format!("cannot write plot [path={}]", self.path))?;
+
+        info!("plot written [path={}]", self.path);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Subcommand, Serialize)]
+enum Commands {
+    ///Success rate of the Q2 amplified estimator or the Q3 projection for every epsilon against the theoretical 1-delta
+    SuccessRate(SuccessRateCommand),
+    ///Histogram of single estimates of a Q2 or Q3 estimator with the true L2 norm marked
+    Histogram(HistogramCommand),
+    ///Rate at which the secretary strategy hires the best candidate for every cutoff against the theoretical curve
+    Secretary(SecretaryCommand),
+}
@@ -0,0 +1,81 @@
+use std::f64::consts::E;
+
+use anyhow::Result;
+use clap::Args;
+use nameof::name_of;
+use serde::Serialize;
+
+use crate::{common::validation::validate_count, error::CliError, q1};
+
+use super::chart::Chart;
+
+#[derive(Debug, Args, Serialize)]
+pub struct SecretaryCommand {
+    ///Amount of candidates
+    #[clap(short, long, default_value = "100")]
+    array_length: usize,
+    ///Amount of times the strategy runs for every cutoff
+    #[clap(short, long, default_value = "1000")]
+    repeat_count: usize,
+    ///Amount of cutoffs evenly spread over the candidates
+    #[clap(short, long, default_value = "20")]
+    cutoffs: usize,
+}
+
+impl SecretaryCommand {
+    pub fn new(array_length: usize, repeat_count: usize, cutoffs: usize) -> Self {
+        SecretaryCommand {
+            array_length,
+            repeat_count,
+            cutoffs,
+        }
+    }
+
+    pub fn chart(&self) -> Result<Chart> {
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+        validate_count(name_of!(cutoffs in Self), self.cutoffs)?;
+
+        if self.array_length < 2 {
+            return Err(CliError::invalid_parameter(
+                name_of!(array_length in Self),
+                self.array_length,
+                "must be at least 2",
+            )
+            .into());
+        }
+
+        let n = self.array_length;
+        let mut cutoffs = (1..=self.cutoffs)
+            .map(|index| (index * (n - 1) / self.cutoffs).max(1))
+            .collect::<Vec<usize>>();
+        cutoffs.dedup();
+
+        let measured = cutoffs
+            .iter()
+            .map(|cutoff| {
+                let hit_rate = q1::hit_rate((0..n).collect(), *cutoff, self.repeat_count)?;
+
+                Ok((*cutoff as f64 / n as f64, hit_rate))
+            })
+            .collect::<Result<Vec<(f64, f64)>>>()?;
+
+        // skipping k of n candidates hires the best one with probability about (k/n)ln(n/k)
+        let theoretical = (1..n)
+            .step_by((n / 100).max(1))
+            .map(|cutoff| {
+                let fraction = cutoff as f64 / n as f64;
+
+                (fraction, -fraction * fraction.ln())
+            })
+            .collect();
+
+        Ok(Chart::new(
+            &format!("Secretary problem with {} candidates", n),
+            "cutoff / candidates",
+            "best candidate hire rate",
+        )
+        .line("measured", measured)
+        .line("theoretical (k/n)ln(n/k)", theoretical)
+        .vertical_line("cutoff n/e", 1.0 / E))
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{Error, Result};
+use clap::{ArgEnum, Args};
+use nameof::name_of;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    common::{algorithm::Algorithm, registry::Parameters, store::ResultStore},
+    error::CliError,
+    run,
+};
+
+use super::chart::Chart;
+
+#[derive(Debug, Clone, Copy, ArgEnum, Serialize)]
+pub enum Question {
+    ///Amplified L2 estimator
+    Q2,
+    ///Johnson-Lindenstrauss projection
+    Q3,
+}
+
+impl Question {
+    fn registered_name(self) -> &'static str {
+        match self {
+            Question::Q2 => "q2-amplificated",
+            Question::Q3 => "q3",
+        }
+    }
+
+    /// Name the runs of the question are recorded under
+    fn algorithm_name(self) -> &'static str {
+        match self {
+            Question::Q2 => "Q2 Amplificated Algorithm",
+            Question::Q3 => "Q3 Algorithm",
+        }
+    }
+
+    /// Result field holding the success rate in percent
+    fn rate_field(self) -> &'static str {
+        match self {
+            Question::Q2 => "succession_ratio",
+            Question::Q3 => "hit_percent",
+        }
+    }
+}
+
+#[derive(Debug, Args, Serialize)]
+pub struct SuccessRateCommand {
+    ///Question whose estimator is plotted
+    #[clap(arg_enum)]
+    question: Question,
+    ///Comma separated epsilon values the estimator runs with
+    #[clap(
+        short,
+        long,
+        use_value_delimiter = true,
+        default_value = "0.2,0.3,0.4,0.5,0.6,0.7,0.8"
+    )]
+    epsilons: Vec<f64>,
+    ///delta value, the theoretical success rate is 1-delta
+    #[clap(short, long, default_value = "0.1")]
+    delta: f64,
+    ///Amount of times the estimator runs for every epsilon
+    #[clap(short, long, default_value = "100")]
+    repeat_count: usize,
+    ///Plot the latest recorded run of every epsilon with this delta instead of running the estimator
+    #[clap(long)]
+    from_store: bool,
+}
+
+impl SuccessRateCommand {
+    /// Chart of the runs of `question` recorded with `delta`
+    pub fn recorded(question: Question, delta: f64) -> Self {
+        SuccessRateCommand {
+            question,
+            epsilons: Vec::new(),
+            delta,
+            repeat_count: 0,
+            from_store: true,
+        }
+    }
+
+    pub fn chart(&self, store: &ResultStore) -> Result<Chart> {
+        let mut points = if self.from_store {
+            self.recorded_points(store)?
+        } else {
+            self.measured_points()?
+        };
+
+        points.sort_by(|(first, _), (second, _)| first.total_cmp(second));
+
+        Ok(Chart::new(
+            &format!("{} success rate", self.question.algorithm_name()),
+            "epsilon",
+            "success rate",
+        )
+        .line("measured", points)
+        .horizontal_line(
+            &format!("1 - delta = {}", 1.0 - self.delta),
+            1.0 - self.delta,
+        ))
+    }
+
+    fn measured_points(&self) -> Result<Vec<(f64, f64)>> {
+        let registry = run::registry();
+
+        self.epsilons
+            .iter()
+            .map(|epsilon| {
+                let parameters = match json!({
+                    "epsilon": epsilon,
+                    "delta": self.delta,
+                    "repeat_count": self.repeat_count,
+                }) {
+                    Value::Object(parameters) => parameters,
+                    _ => Parameters::new(),
+                };
+
+                let (algorithm, _) =
+                    registry.create(self.question.registered_name(), &parameters)?;
+                let report = algorithm.run()?;
+
+                Ok((*epsilon, self.rate(&report.result)?))
+            })
+            .collect()
+    }
+
+    /// Latest complete recorded run of every epsilon, later records replace earlier ones
+    fn recorded_points(&self, store: &ResultStore) -> Result<Vec<(f64, f64)>> {
+        let mut points: Vec<(f64, f64)> = Vec::new();
+
+        for (_, record) in store.records()? {
+            let report = record.report;
+
+            if report.algorithm != self.question.algorithm_name()
+                || report.partial
+                || report.parameter("delta") != Some(self.delta)
+            {
+                continue;
+            }
+
+            if let Some(epsilon) = report.parameter("epsilon") {
+                let rate = self.rate(&report.result)?;
+
+                points.retain(|(recorded, _)| *recorded != epsilon);
+                points.push((epsilon, rate));
+            }
+        }
+
+        if points.is_empty() {
+            return Err(CliError::invalid_parameter(
+                name_of!(delta in Self),
+                self.delta,
+                &format!("has no recorded runs of {}", self.question.algorithm_name()),
+            )
+            .into());
+        }
+
+        Ok(points)
+    }
+
+    /// Success rate as a fraction out of the percent stored in the result
+    fn rate(&self, result: &Value) -> Result<f64> {
+        result
+            .get(self.question.rate_field())
+            .and_then(Value::as_f64)
+            .map(|percent| percent / 100.0)
+            .ok_or_else(|| {
+                Error::msg(format!(
+                    "result has no success rate [field={}]",
+                    self.question.rate_field()
+                ))
+            })
+    }
+}
@@ -3,20 +3,33 @@ use clap::Args;
 use log::debug;
 use nameof::name_of;
 use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    common::{algorithm::Algorithm, repeat::IntoRepeat, reduce::IntoReduce},
+    common::{
+        algorithm::Algorithm,
+        random,
+        reduce::IntoReduce,
+        registry::Registry,
+        repeat::IntoRepeat,
+        run_report::RunReport,
+        stopping,
+        validation::{validate_count, value_range},
+        with_name::IntoWithName,
+    },
     extensions::vec_extensions::SampleUniformVecExtensions,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Q1Result {
-    _hit_percent: f32,
-    _average: f32,
+    hit_percent: f32,
+    average: f32,
 }
 
+/// Skips the first `cutoff` candidates and hires the first one at least as good as all of them
 pub struct SecreteryProblemAlgorithm {
     array: Vec<usize>,
+    cutoff: usize,
 }
 
 impl Algorithm for SecreteryProblemAlgorithm {
@@ -34,23 +47,23 @@ impl Algorithm for SecreteryProblemAlgorithm {
     fn run_internal<F: Fn() + Send + Sync>(&self, update_progress: F) -> Result<usize> {
         debug!("run_internal started");
 
-        let n = self.array.len();
         let mut permutation = self.array.clone();
-        permutation.shuffle(&mut rand::thread_rng());
+        permutation.shuffle(&mut random::rng());
 
         let threshold = permutation
             .iter()
-            .take(n / 2)
+            .take(self.cutoff)
             .max()
             .ok_or(Error::msg("failed to get maximum"))?;
 
+        // the last candidate is hired when none beats the threshold
         let result = permutation
             .iter()
-            .skip(self.array.len() / 2)
+            .skip(self.cutoff)
             .filter(|item| **item >= *threshold)
             .next()
             .map_or(
-                self.array
+                permutation
                     .last()
                     .ok_or(Error::msg("no items in array"))?
                     .clone(),
@@ -65,23 +78,51 @@ impl Algorithm for SecreteryProblemAlgorithm {
     }
 }
 
-#[derive(Debug, Args)]
+#[derive(Debug, Args, Serialize, Deserialize)]
 pub struct Q1Command {
     ///Length of the array that will be generated
     #[clap(short, long, default_value = "1000")]
     array_length: usize,
+    ///Smallest value of the generated array items
+    #[clap(long, default_value = "0")]
+    min_value: usize,
+    ///Largest value of the generated array items
+    #[clap(long, default_value = "9999")]
+    max_value: usize,
     ///Amount of times the algorithm will run
     #[clap(short, long, default_value = "1000")]
     repeat_count: usize,
+    ///Stop repeating once at least 30 repetitions completed and the 95% confidence interval of the hit rate is narrower than this width
+    #[clap(long)]
+    target_width: Option<f64>,
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register("q1", "Run Question1 program", Q1Command::algorithm);
 }
 
 impl Q1Command {
-    pub fn invoke(&self) -> Result<()> {
-        let array = Vec::with_random_items_in_range(self.array_length, || 0..10000);
+    pub fn invoke(&self) -> Result<RunReport> {
+        self.algorithm()?.run()
+    }
+
+    pub fn algorithm(
+        &self,
+    ) -> Result<impl Algorithm<Output = impl Serialize> + Sync + Send + 'static> {
+        validate_count(name_of!(array_length in Self), self.array_length)?;
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+        stopping::validate_target_width(self.target_width)?;
 
-        SecreteryProblemAlgorithm { array: array.clone() }
+        let values = value_range(self.min_value, self.max_value)?;
+        let array = Vec::with_random_items_in_range(self.array_length, || values.clone());
+        let max = *array.iter().max().ok_or(Error::msg("Failed to get max"))?;
+
+        let cutoff = array.len() / 2;
+
+        Ok(SecreteryProblemAlgorithm { array, cutoff }
             .repeat(self.repeat_count)
-            .reduce(move |series| {
+            .stop_early(self.target_width, move |result| *result == max)
+            .reduce(|series, (array, _)| {
                 let max = array.iter().max().ok_or(Error::msg("Failed to get max"))?;
 
                 let hit_count = series.iter().filter(|result| **result == *max).count();
@@ -89,10 +130,42 @@ impl Q1Command {
                 let average = series.iter().sum::<usize>() as f32 / series.len() as f32;
 
                 Ok(Q1Result {
-                    _hit_percent: hit_percent,
-                    _average: average,
+                    hit_percent,
+                    average,
                 })
             })
-            .run()
+            .with_name("Q1 Algorithm".into()))
+    }
+}
+
+/// Fraction of `repeat_count` runs in which skipping the first `cutoff` candidates of a random
+/// order of `array` ends up hiring its maximum
+pub fn hit_rate(array: Vec<usize>, cutoff: usize, repeat_count: usize) -> Result<f64> {
+    let max = *array.iter().max().ok_or(Error::msg("Failed to get max"))?;
+
+    SecreteryProblemAlgorithm { array, cutoff }
+        .repeat(repeat_count)
+        .reduce(move |series, _| {
+            let hit_count = series.iter().filter(|result| **result == max).count();
+
+            Ok(hit_count as f64 / series.len() as f64)
+        })
+        .with_name(format!("Secretery problem [cutoff={}]", cutoff))
+        .run_output()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::LN_2;
+
+    use super::*;
+
+    #[test]
+    fn half_cutoff_hires_the_best_candidate_at_the_theoretical_rate() {
+        let n = 100;
+        let rate = random::scoped(Some(1), || hit_rate((0..n).collect(), n / 2, 20000)).unwrap();
+
+        // the rate of a cutoff at n/2 tends to ln(2)/2 ≈ 0.3466, it is 0.3491 for n = 100
+        assert!((rate - LN_2 / 2.0).abs() < 0.02, "{}", rate);
     }
 }
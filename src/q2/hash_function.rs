@@ -1,6 +1,8 @@
 use std::{collections::HashMap, hash::Hash};
 use rand::Rng;
 
+use crate::common::random;
+
 pub struct HashFunction<TSourceItem: Hash + Eq,TTargetItem : Clone + Copy> {
     map: HashMap<TSourceItem,TTargetItem>,
 }
@@ -12,12 +14,12 @@ impl<TSourceItem: Hash + Eq,TTargetItem : Clone + Copy> HashFunction<TSourceItem
     ) -> Self {
         let target = target.into_iter().collect::<Vec<TTargetItem>>();
 
-        let mut thread_rng = rand::thread_rng();
+        let mut rng = random::rng();
 
         HashFunction {
             map: source
                 .into_iter()
-                .map(|item| (item, target[thread_rng.gen_range(0..target.len())]))
+                .map(|item| (item, target[rng.gen_range(0..target.len())]))
                 .collect(),
         }
     }
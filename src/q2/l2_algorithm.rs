@@ -43,3 +43,26 @@ impl Algorithm for L2Algorithm {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{execution::ExecutionPolicy, random, repeat::IntoRepeat};
+
+    fn estimates(seed: u64, policy: ExecutionPolicy) -> Vec<f64> {
+        let repeat = L2Algorithm::new((1..=20).map(f64::from).collect())
+            .repeat(64)
+            .with_execution(policy);
+
+        random::scoped(Some(seed), || repeat.run_internal(|| {})).unwrap()
+    }
+
+    #[test]
+    fn seeded_repetitions_do_not_depend_on_the_scheduling() {
+        let sequential = estimates(7, ExecutionPolicy::Sequential);
+
+        assert_eq!(estimates(7, ExecutionPolicy::Parallel), sequential);
+        assert_eq!(estimates(7, ExecutionPolicy::Parallel), sequential);
+        assert_ne!(estimates(8, ExecutionPolicy::Sequential), sequential);
+    }
+}
@@ -1,39 +1,90 @@
 mod hash_function;
 mod l2_algorithm;
 mod q2_amplificated_command;
+mod q2_compare_command;
 mod q2_naive_command;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
+use nameof::name_of;
+use serde::{Deserialize, Serialize};
 
-use crate::extensions::vec_extensions::SampleUniformVecExtensions;
+use crate::{
+    common::{
+        registry::Registry,
+        run_report::RunReport,
+        validation::{validate_count, value_range},
+    },
+    extensions::vec_extensions::SampleUniformVecExtensions,
+};
 
-use self::{q2_amplificated_command::Q2AmplificatedCommand, q2_naive_command::Q2NaiveCommand};
+use self::q2_compare_command::Q2CompareCommand;
 
-#[derive(Debug, Args)]
+pub use self::{q2_amplificated_command::Q2AmplificatedCommand, q2_naive_command::Q2NaiveCommand};
+
+#[derive(Debug, Args, Serialize)]
 pub struct Q2Command {
     #[clap(subcommand)]
     command: Commands,
 }
 
-impl Q2Command {
-    pub fn invoke(&self) -> Result<()> {
-        let array = Vec::with_random_items_in_range(1000, || 0..=2)
-            .into_iter()
-            .map(|item| item as f64)
-            .collect();
+/// Vector whose L2 norm is estimated, generated with uniformly distributed integer items
+#[derive(Debug, Args, Serialize, Deserialize)]
+pub struct ArrayArguments {
+    ///Length of the array that will be generated
+    #[clap(short, long, default_value = "1000")]
+    array_length: usize,
+    ///Smallest value of the generated array items
+    #[clap(long, default_value = "0")]
+    min_value: i32,
+    ///Largest value of the generated array items
+    #[clap(long, default_value = "2")]
+    max_value: i32,
+}
+
+impl ArrayArguments {
+    fn generate(&self) -> Result<Vec<f64>> {
+        validate_count(name_of!(array_length in Self), self.array_length)?;
+        let values = value_range(self.min_value, self.max_value)?;
+
+        Ok(
+            Vec::with_random_items_in_range(self.array_length, || values.clone())
+                .into_iter()
+                .map(|item| item as f64)
+                .collect(),
+        )
+    }
+}
 
+pub fn register(registry: &mut Registry) {
+    registry.register(
+        "q2-naive",
+        "Runs the L2 algorithm without amplification",
+        Q2NaiveCommand::algorithm,
+    );
+    registry.register(
+        "q2-amplificated",
+        "Runs the L2 algorithm with amplification",
+        Q2AmplificatedCommand::algorithm,
+    );
+}
+
+impl Q2Command {
+    pub fn invoke(&self) -> Result<RunReport> {
         match &self.command {
-            Commands::Naive(command) => command.invoke(array),
-            Commands::Amplificated(command) => command.invoke(array),
+            Commands::Naive(command) => command.invoke(),
+            Commands::Amplificated(command) => command.invoke(),
+            Commands::Compare(command) => command.invoke(),
         }
     }
 }
 
-#[derive(Debug, Subcommand)]
+#[derive(Debug, Subcommand, Serialize)]
 enum Commands {
     ///Runs the L2 algorithm without amplification
     Naive(Q2NaiveCommand),
     ///Runs the L2 algorithm with amplification
     Amplificated(Q2AmplificatedCommand),
+    ///Runs the L2 algorithm with and without amplification and compares them side by side and paired on the same input
+    Compare(Q2CompareCommand),
 }
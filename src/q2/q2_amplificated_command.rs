@@ -1,74 +1,143 @@
 use anyhow::{Ok, Result};
 use clap::Args;
 use nameof::name_of;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
-        algorithm::Algorithm, reduce::IntoReduce, repeat::IntoRepeat, with_name::IntoWithName,
+        algorithm::Algorithm, execution::ExecutionPolicy, reduce::IntoReduce, repeat::IntoRepeat,
+        run_report::RunReport, stopping, tolerate_failures::Failures, validation::validate_count,
+        with_name::IntoWithName,
     },
+    error::CliError,
     extensions::vec_extensions::L2NormVecExtension,
 };
 
-use super::l2_algorithm::L2Algorithm;
+use super::{l2_algorithm::L2Algorithm, ArrayArguments};
 
-#[derive(Debug, Args)]
+#[derive(Debug, Args, Serialize, Deserialize)]
 pub struct Q2AmplificatedCommand {
+    #[clap(flatten)]
+    #[serde(flatten)]
+    array: ArrayArguments,
     ///epsilon value
     #[clap(short, long, default_value = "0.5")]
     epsilon: f64,
     ///delta value
     #[clap(short, long, default_value = "0.01")]
     delta: f64,
+    ///Amount of times the amplified estimator will run
+    #[clap(short, long, default_value = "1000")]
+    repeat_count: usize,
+    ///Stop repeating once at least 30 repetitions completed and the 95% confidence interval of the hit rate is narrower than this width
+    #[clap(long)]
+    target_width: Option<f64>,
+    ///Keep running when repetitions fail and report the failures instead of failing on the first one
+    #[clap(long)]
+    tolerate_failures: bool,
 }
 
 impl Q2AmplificatedCommand {
-    pub fn invoke(&self, array: Vec<f64>) -> Result<()> {
-        if self.delta < 0_f64 || self.delta > 1_f64 {
-            Err(anyhow::Error::msg(format!(
-                "delta must be between 0 and 1 [{}={}]",
-                name_of!(delta in Self),
-                self.delta
-            )))
-        } else if self.epsilon < 0_f64 || self.epsilon > 1_f64 {
-            Err(anyhow::Error::msg(format!(
-                "epsilon must be between 0 and 1 [{}={}]",
-                name_of!(epsilon in Self),
-                self.epsilon
-            )))
-        } else {
-            L2Algorithm::new(array.clone())
-                .repeat((9.0 / self.epsilon).ceil() as usize)
-                .reduce(|series| Ok(series.iter().sum::<f64>() / series.len() as f64))
-                .repeat((18.0 * (2.0 / self.delta).ln() + 1.0).floor() as usize)
-                .reduce(|mut series| {
-                    series.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                    let mid = series.len() / 2;
-                    Ok(series[mid])
-                })
-                .repeat(1000)
-                .reduce(move |series| {
-                    let l2_norm: f64 = array.l2_norm();
-                    let lower_bar = (1.0 - self.epsilon) * l2_norm;
-                    let upper_bar = (1.0 + self.epsilon) * l2_norm;
-
-                    let succession_count = series
-                        .iter()
-                        .filter(|num| **num >= lower_bar && **num <= upper_bar)
-                        .count();
-
-                    let succession_ratio = succession_count as f64 / series.len() as f64;
-
-                    Ok(Q2AmplificatedAlgorithmResult {
-                        _succession_ratio: succession_ratio * 100.0,
-                    })
+    pub fn invoke(&self) -> Result<RunReport> {
+        self.algorithm()?.run()
+    }
+
+    pub fn algorithm(
+        &self,
+    ) -> Result<impl Algorithm<Output = impl Serialize> + Sync + Send + 'static> {
+        validate_parameters(self.epsilon, self.delta)?;
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+        stopping::validate_target_width(self.target_width)?;
+
+        let array = self.array.generate()?;
+        let l2_norm = array.l2_norm();
+        let lower_bar = (1.0 - self.epsilon) * l2_norm;
+        let upper_bar = (1.0 + self.epsilon) * l2_norm;
+
+        Ok(amplified_estimator(array, self.epsilon, self.delta)
+            .repeat(self.repeat_count)
+            .stop_early(self.target_width, move |median| {
+                *median >= lower_bar && *median <= upper_bar
+            })
+            .tolerate_failures(self.tolerate_failures)
+            .reduce(move |mut repetitions, _| {
+                repetitions.require_outputs()?;
+
+                let series = repetitions.outputs;
+                let succession_count = series
+                    .iter()
+                    .filter(|num| **num >= lower_bar && **num <= upper_bar)
+                    .count();
+
+                let succession_ratio = succession_count as f64 / series.len() as f64;
+
+                Ok(Q2AmplificatedAlgorithmResult {
+                    succession_ratio: succession_ratio * 100.0,
+                    failures: repetitions.failures,
                 })
-                .with_name("Q2 Amplificated Algorithm".into())
-                .run()
-        }
+            })
+            .with_name("Q2 Amplificated Algorithm".into()))
+    }
+
+    /// Every amplified estimate along with the L2 norm of the generated array
+    pub fn estimates(&self) -> Result<(Vec<f64>, f64)> {
+        validate_parameters(self.epsilon, self.delta)?;
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+
+        let array = self.array.generate()?;
+        let l2_norm = array.l2_norm();
+        let estimates = amplified_estimator(array, self.epsilon, self.delta)
+            .repeat(self.repeat_count)
+            .with_name("Q2 Amplificated estimates".into())
+            .run_output()?;
+
+        Ok((estimates, l2_norm))
+    }
+}
+
+pub(super) fn validate_parameters(epsilon: f64, delta: f64) -> Result<()> {
+    if !(delta > 0_f64 && delta < 1_f64) {
+        Err(CliError::invalid_parameter(name_of!(delta), delta, "must be between 0 and 1").into())
+    } else if !(epsilon > 0_f64 && epsilon < 1_f64) {
+        Err(
+            CliError::invalid_parameter(name_of!(epsilon), epsilon, "must be between 0 and 1")
+                .into(),
+        )
+    } else {
+        Ok(())
     }
 }
 
-#[derive(Debug)]
+/// Median of means of L2 estimates, within `(1±epsilon)` of the norm with probability `1-delta`
+pub(super) fn amplified_estimator(
+    array: Vec<f64>,
+    epsilon: f64,
+    delta: f64,
+) -> impl Algorithm<Output = f64> + Sync + Send {
+    L2Algorithm::new(array)
+        .repeat((9.0 / epsilon).ceil() as usize)
+        // a single estimate is too cheap to be worth scheduling on the pool
+        .with_execution(ExecutionPolicy::Sequential)
+        .reduce(|series, _| Ok(series.iter().sum::<f64>() / series.len() as f64))
+        .with_name("Inner mean".into())
+        .repeat((18.0 * (2.0 / delta).ln() + 1.0).floor() as usize)
+        .reduce(|mut series, _| {
+            if series.iter().any(|item| item.is_nan()) {
+                return Err(CliError::Numeric(
+                    "cannot take the median of a series containing NaN".into(),
+                )
+                .into());
+            }
+
+            series.sort_by(|a, b| a.total_cmp(b));
+            let mid = series.len() / 2;
+            Ok(series[mid])
+        })
+        .with_name("Median group".into())
+}
+
+#[derive(Debug, Serialize)]
 struct Q2AmplificatedAlgorithmResult {
-    _succession_ratio: f64,
+    succession_ratio: f64,
+    failures: Failures,
 }
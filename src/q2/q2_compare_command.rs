@@ -0,0 +1,116 @@
+use anyhow::Result;
+use clap::Args;
+use nameof::name_of;
+use serde::Serialize;
+
+use crate::{
+    common::{
+        compare::Compare, map::IntoMap, repeat::IntoRepeat, run_report::RunReport,
+        validation::validate_count, with_name::IntoWithName, zip::IntoZip,
+    },
+    extensions::vec_extensions::L2NormVecExtension,
+};
+
+use super::{
+    l2_algorithm::L2Algorithm,
+    q2_amplificated_command::{amplified_estimator, validate_parameters},
+    ArrayArguments,
+};
+
+#[derive(Debug, Args, Serialize)]
+pub struct Q2CompareCommand {
+    #[clap(flatten)]
+    #[serde(flatten)]
+    array: ArrayArguments,
+    ///epsilon value
+    #[clap(short, long, default_value = "0.5")]
+    epsilon: f64,
+    ///delta value
+    #[clap(short, long, default_value = "0.01")]
+    delta: f64,
+    ///Amount of times each estimator will run
+    #[clap(short, long, default_value = "1000")]
+    repeat_count: usize,
+}
+
+impl Q2CompareCommand {
+    pub fn invoke(&self) -> Result<RunReport> {
+        validate_parameters(self.epsilon, self.delta)?;
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+
+        let array = self.array.generate()?;
+
+        let l2_norm = array.l2_norm();
+        let lower_bar = (1.0 - self.epsilon) * l2_norm;
+        let upper_bar = (1.0 + self.epsilon) * l2_norm;
+        let summarize =
+            move |series: Vec<f64>| L2EstimatorComparison::new(&series, lower_bar, upper_bar);
+        let (epsilon, delta) = (self.epsilon, self.delta);
+
+        Compare::new("Q2 Estimator Comparison".into())
+            .add(
+                L2Algorithm::new(array.clone())
+                    .repeat(self.repeat_count)
+                    .map(summarize)
+                    .with_name("Naive".into()),
+            )
+            .add(
+                amplified_estimator(array.clone(), self.epsilon, self.delta)
+                    .repeat(self.repeat_count)
+                    .map(summarize)
+                    .with_name("Amplificated".into()),
+            )
+            .add(
+                array
+                    .into_zip(L2Algorithm::new, |array| {
+                        amplified_estimator(array, epsilon, delta)
+                    })
+                    .repeat(self.repeat_count)
+                    .map(move |pairs| PairedEstimatorComparison::new(&pairs, l2_norm))
+                    .with_name("Paired".into()),
+            )
+            .run()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct L2EstimatorComparison {
+    average: f64,
+    succession_ratio: f64,
+}
+
+impl L2EstimatorComparison {
+    fn new(series: &[f64], lower_bar: f64, upper_bar: f64) -> Self {
+        let succession_count = series
+            .iter()
+            .filter(|num| **num >= lower_bar && **num <= upper_bar)
+            .count();
+
+        L2EstimatorComparison {
+            average: series.iter().sum::<f64>() / series.len() as f64,
+            succession_ratio: succession_count as f64 / series.len() as f64 * 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PairedEstimatorComparison {
+    /// Percent of the repetitions in which the amplified estimate was closer to the norm
+    /// than the naive estimate drawn next to it
+    amplificated_closer_ratio: f64,
+}
+
+impl PairedEstimatorComparison {
+    fn new(pairs: &[(f64, f64)], l2_norm: f64) -> Self {
+        let closer_count = pairs
+            .iter()
+            .filter(|(naive, amplificated)| {
+                (amplificated - l2_norm).abs() < (naive - l2_norm).abs()
+            })
+            .count();
+
+        PairedEstimatorComparison {
+            amplificated_closer_ratio: closer_count as f64 / pairs.len() as f64 * 100.0,
+        }
+    }
+}
@@ -1,38 +1,69 @@
 use anyhow::Result;
 use clap::Args;
+use nameof::name_of;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
-        algorithm::Algorithm, reduce::IntoReduce, repeat::IntoRepeat, with_name::IntoWithName,
+        algorithm::Algorithm, reduce::IntoReduce, repeat::IntoRepeat, run_report::RunReport,
+        validation::validate_count, with_name::IntoWithName,
     },
     extensions::vec_extensions::L2NormVecExtension,
 };
 
-use super::l2_algorithm::L2Algorithm;
+use super::{l2_algorithm::L2Algorithm, ArrayArguments};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Q2NaiveAlgorithmResult {
-    _average: f64,
-    _l2_norm: f64,
+    average: f64,
+    l2_norm: f64,
 }
 
-#[derive(Debug, Args)]
-pub struct Q2NaiveCommand;
+#[derive(Debug, Args, Serialize, Deserialize)]
+pub struct Q2NaiveCommand {
+    #[clap(flatten)]
+    #[serde(flatten)]
+    array: ArrayArguments,
+    ///Amount of times the algorithm will run
+    #[clap(short, long, default_value = "1000")]
+    repeat_count: usize,
+}
 
 impl Q2NaiveCommand {
-    pub fn invoke(&self, array: Vec<f64>) -> Result<()> {
-        L2Algorithm::new(array.clone())
-            .repeat(1000)
-            .reduce(move |series| {
+    pub fn invoke(&self) -> Result<RunReport> {
+        self.algorithm()?.run()
+    }
+
+    pub fn algorithm(
+        &self,
+    ) -> Result<impl Algorithm<Output = impl Serialize> + Sync + Send + 'static> {
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+
+        Ok(L2Algorithm::new(self.array.generate()?)
+            .repeat(self.repeat_count)
+            .reduce(|series, (array, _)| {
                 let average = series.iter().sum::<f64>() / series.len() as f64;
                 let l2_norm = array.l2_norm();
 
                 Ok(Q2NaiveAlgorithmResult {
-                    _average: average,
-                    _l2_norm: l2_norm,
+                    average,
+                    l2_norm,
                 })
             })
-            .with_name("Q2 Naive Algorithm".into())
-            .run()
+            .with_name("Q2 Naive Algorithm".into()))
+    }
+
+    /// Every single estimate along with the L2 norm of the generated array
+    pub fn estimates(&self) -> Result<(Vec<f64>, f64)> {
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+
+        let array = self.array.generate()?;
+        let l2_norm = array.l2_norm();
+        let estimates = L2Algorithm::new(array)
+            .repeat(self.repeat_count)
+            .with_name("Q2 Naive estimates".into())
+            .run_output()?;
+
+        Ok((estimates, l2_norm))
     }
 }
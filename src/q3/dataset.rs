@@ -0,0 +1,246 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{Error, Result};
+use clap::Args;
+use log::{debug, info};
+use serde::Serialize;
+
+use crate::{
+    common::{
+        algorithm::Algorithm, random, run_report::RunReport, validation::validate_count,
+        with_name::IntoWithName,
+    },
+    extensions::vec_extensions::{L2NormVecExtension, SampleUniformVecExtensions, TryCollect},
+};
+
+use super::{
+    matrix::Matrix,
+    operator::LinearOperator,
+    projection::ProjectionKind,
+    projection_dimension,
+    sparse_matrix::{sparse_squared_distance, SparseLayout, SparseMatrix},
+};
+
+#[derive(Debug, Args, Serialize)]
+pub struct Q3DatasetCommand {
+    ///Path of a matrix file (.csv, .bin or .mtx) with one point per row, generated randomly when not provided
+    #[clap(short, long)]
+    input: Option<String>,
+    ///Path of a matrix file (.csv, .bin or .mtx) the sampled projection will be saved to
+    #[clap(short, long)]
+    save_projection: Option<String>,
+    ///Path of a matrix file (.csv, .bin or .mtx) holding the projection to use instead of sampling one
+    #[clap(short, long)]
+    load_projection: Option<String>,
+    ///Amount of points that will be generated
+    #[clap(short = 'n', long, default_value = "100")]
+    points: usize,
+    ///Dimension of the points that will be generated
+    #[clap(short = 'm', long, default_value = "1000")]
+    dimension: usize,
+}
+
+impl Q3DatasetCommand {
+    pub fn invoke(
+        &self,
+        epsilon: f64,
+        delta: f64,
+        projection: ProjectionKind,
+        sparse: bool,
+        values: RangeInclusive<i32>,
+    ) -> Result<RunReport> {
+        let points = match &self.input {
+            Some(path) => SparseMatrix::read_file(path, SparseLayout::Csr)?,
+            None => {
+                validate_count("points", self.points)?;
+                validate_count("dimension", self.dimension)?;
+
+                let rows = (0..self.points)
+                    .map(|_| {
+                        Vec::with_random_items_in_range(self.dimension, || values.clone())
+                            .into_iter()
+                            .map(|num: i32| num as f64)
+                            .collect()
+                    })
+                    .collect();
+
+                SparseMatrix::from_dense(&Matrix::from_rows(rows)?, SparseLayout::Csr)?
+            }
+        };
+
+        info!(
+            "dataset loaded [points={} dimension={} nonzero_count={}]",
+            points.row_count(),
+            points.column_count(),
+            points.nonzero_count()
+        );
+
+        JonsonLindenshtrassDatasetAlgorithm {
+            input: JonsonLindenshtrassDatasetAlgorithmInput {
+                points,
+                epsilon,
+                delta,
+                projection,
+                sparse,
+                save_projection: self.save_projection.clone(),
+                load_projection: self.load_projection.clone(),
+            },
+        }
+        .with_name("Q3 Dataset Algorithm".into())
+        .run()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JonsonLindenshtrassDatasetAlgorithmInput {
+    /// One point per row
+    points: SparseMatrix,
+    epsilon: f64,
+    delta: f64,
+    projection: ProjectionKind,
+    sparse: bool,
+    save_projection: Option<String>,
+    load_projection: Option<String>,
+}
+
+struct JonsonLindenshtrassDatasetAlgorithm {
+    input: JonsonLindenshtrassDatasetAlgorithmInput,
+}
+
+impl Algorithm for JonsonLindenshtrassDatasetAlgorithm {
+    type Input = JonsonLindenshtrassDatasetAlgorithmInput;
+    type Output = Q3DatasetResult;
+
+    fn name(&self) -> String {
+        "Jonson Lindenshtrass Dataset Algorithm".into()
+    }
+
+    fn input(&self) -> Self::Input {
+        self.input.clone()
+    }
+
+    fn run_internal<F: Fn() + Send + Sync>(&self, update_progress: F) -> Result<Q3DatasetResult> {
+        debug!("run_internal started");
+
+        let points = &self.input.points;
+        let (point_count, dimension) = (points.row_count(), points.column_count());
+
+        if point_count == 0 {
+            return Err(Error::msg("dataset contains no points"));
+        }
+
+        // the lemma guarantee must hold for every pair simultaneously, so the
+        // failure probability is split between them (union bound), projecting to
+        // more dimensions than the points have would not reduce them
+        let pair_count = point_count * (point_count - 1) / 2;
+        let k = projection_dimension(
+            self.input.epsilon,
+            self.input.delta / pair_count.max(1) as f64,
+        )
+        .min(dimension);
+        let rng = &mut random::rng();
+
+        let operator: Box<dyn LinearOperator> = if self.input.sparse {
+            let matrix = match &self.input.load_projection {
+                Some(path) => SparseMatrix::read_file(path, SparseLayout::Csc)?,
+                None => self.input.projection.generate_sparse(k, dimension, rng)?,
+            };
+
+            if let Some(path) = &self.input.save_projection {
+                matrix.write_file(path)?;
+            }
+
+            Box::new(matrix)
+        } else {
+            let matrix = match &self.input.load_projection {
+                Some(path) => Matrix::read_file(path)?,
+                None => self.input.projection.generate(k, dimension, rng)?,
+            };
+
+            if let Some(path) = &self.input.save_projection {
+                matrix.write_file(path)?;
+            }
+
+            Box::new(matrix)
+        };
+
+        let projected: Vec<Vec<f64>> = (0..point_count)
+            .map(|i| {
+                let (indices, values) = points.major(i);
+
+                operator.multiply_sparse(indices, values)
+            })
+            .try_collect()?;
+
+        let mut ratios = Vec::new();
+
+        for i in 0..point_count {
+            let (left_indices, left_values) = points.major(i);
+
+            for j in (i + 1)..point_count {
+                let (right_indices, right_values) = points.major(j);
+                let distance =
+                    sparse_squared_distance(left_indices, left_values, right_indices, right_values);
+
+                if distance > 0.0 {
+                    ratios.push(difference(&projected[i], &projected[j]).l2_norm() / distance);
+                }
+            }
+        }
+
+        if ratios.is_empty() {
+            return Err(Error::msg("dataset contains no distinct pairs of points"));
+        }
+
+        ratios.sort_by(|a, b| a.total_cmp(b));
+
+        let quantile = |q: f64| ratios[((ratios.len() - 1) as f64 * q).round() as usize];
+
+        let within_count = ratios
+            .iter()
+            .filter(|ratio| (**ratio - 1.0).abs() <= self.input.epsilon)
+            .count();
+
+        let result = Q3DatasetResult {
+            projection: self.input.projection,
+            projection_dimension: operator.row_count(),
+            pair_count: ratios.len(),
+            min_ratio: quantile(0.0),
+            p5_ratio: quantile(0.05),
+            p25_ratio: quantile(0.25),
+            median_ratio: quantile(0.5),
+            p75_ratio: quantile(0.75),
+            p95_ratio: quantile(0.95),
+            max_ratio: quantile(1.0),
+            max_distortion: (quantile(0.0) - 1.0).abs().max(quantile(1.0) - 1.0),
+            within_epsilon_percent: within_count as f64 / ratios.len() as f64 * 100.0,
+        };
+
+        debug!("run_internal finished");
+
+        update_progress();
+
+        Ok(result)
+    }
+}
+
+fn difference(left: &[f64], right: &[f64]) -> Vec<f64> {
+    left.iter().zip(right.iter()).map(|(a, b)| a - b).collect()
+}
+
+/// Distribution of the squared pairwise distance ratios `|f(u)-f(v)|^2 / |u-v|^2`
+#[derive(Debug, Serialize)]
+pub struct Q3DatasetResult {
+    projection: ProjectionKind,
+    projection_dimension: usize,
+    pair_count: usize,
+    min_ratio: f64,
+    p5_ratio: f64,
+    p25_ratio: f64,
+    median_ratio: f64,
+    p75_ratio: f64,
+    p95_ratio: f64,
+    max_ratio: f64,
+    max_distortion: f64,
+    within_epsilon_percent: f64,
+}
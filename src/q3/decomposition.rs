@@ -0,0 +1,394 @@
+use anyhow::Result;
+
+use crate::extensions::vec_extensions::EuclideanNormExtension;
+
+use super::matrix::{Matrix, MatrixError};
+
+/// Relative tolerance below which two columns are considered orthogonal by the Jacobi SVD
+const JACOBI_TOLERANCE: f64 = 1e-12;
+const JACOBI_SWEEP_LIMIT: usize = 100;
+
+/// Thin QR decomposition `A = QR` of an m x n matrix with m >= n
+#[derive(Debug, Clone)]
+pub struct QrDecomposition {
+    /// m x n matrix with orthonormal columns
+    pub q: Matrix<f64>,
+    /// n x n upper triangular matrix
+    pub r: Matrix<f64>,
+}
+
+/// Thin singular value decomposition `A = U diag(s) V^T`, singular values in descending order
+#[derive(Debug, Clone)]
+pub struct SingularValueDecomposition {
+    pub u: Matrix<f64>,
+    pub singular_values: Vec<f64>,
+    pub v: Matrix<f64>,
+}
+
+impl SingularValueDecomposition {
+    /// Rank k approximation `U_k diag(s_k) V_k^T` built from the leading singular triplets
+    pub fn truncated(&self, k: usize) -> Result<Matrix<f64>> {
+        let k = k.min(self.singular_values.len());
+
+        if k == 0 {
+            return Ok(Matrix::zeros(self.u.row_count(), self.v.row_count()));
+        }
+
+        let u = Matrix::from_rows(
+            self.u
+                .iter_rows()
+                .map(|row| {
+                    row.iter()
+                        .zip(self.singular_values.iter())
+                        .take(k)
+                        .map(|(item, singular_value)| item * singular_value)
+                        .collect()
+                })
+                .collect(),
+        )?;
+        let v_transpose = Matrix::from_rows(
+            self.v
+                .iter_columns()
+                .take(k)
+                .map(|column| column.iter().copied().collect())
+                .collect(),
+        )?;
+
+        Ok((&u * &v_transpose)?)
+    }
+}
+
+impl Matrix<f64> {
+    /// Householder QR decomposition, requires at least as many rows as columns
+    pub fn qr(&self) -> Result<QrDecomposition> {
+        let m = self.row_count();
+        let n = self.column_count();
+
+        if n == 0 {
+            return Err(MatrixError::Empty {
+                operation: "decompose",
+                shape: self.shape(),
+            }
+            .into());
+        }
+
+        if m < n {
+            return Err(MatrixError::Underdetermined {
+                operation: "QR decompose",
+                shape: self.shape(),
+            }
+            .into());
+        }
+
+        let mut columns = columns_of(self);
+        let mut reflectors = Vec::with_capacity(n);
+
+        for k in 0..n {
+            let x = &columns[k][k..];
+            let norm = x.euclidean_norm();
+            let alpha = if x[0] > 0.0 { -norm } else { norm };
+
+            let mut reflector = x.to_vec();
+            reflector[0] -= alpha;
+
+            let reflector_norm = reflector.euclidean_norm();
+
+            if reflector_norm > 0.0 {
+                reflector
+                    .iter_mut()
+                    .for_each(|item| *item /= reflector_norm);
+
+                for column in columns.iter_mut().skip(k) {
+                    reflect(&reflector, &mut column[k..]);
+                }
+            }
+
+            reflectors.push(reflector);
+        }
+
+        let r = Matrix::from_rows(
+            (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| if i <= j { columns[j][i] } else { 0.0 })
+                        .collect()
+                })
+                .collect(),
+        )?;
+
+        // Q is the product of the reflectors applied to the first n columns of the identity
+        let mut q_columns = (0..n)
+            .map(|j| (0..m).map(|i| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect::<Vec<Vec<f64>>>();
+
+        for (k, reflector) in reflectors.iter().enumerate().rev() {
+            for column in q_columns.iter_mut() {
+                reflect(reflector, &mut column[k..]);
+            }
+        }
+
+        Ok(QrDecomposition {
+            q: Matrix::from_rows(q_columns)?.transpose(),
+            r,
+        })
+    }
+
+    /// Solves `min |Ax - b|` through the QR decomposition of A
+    pub fn solve_least_squares(&self, b: &[f64]) -> Result<Vec<f64>> {
+        if b.len() != self.row_count() {
+            return Err(MatrixError::DimensionMismatch {
+                operation: "solve",
+                left: self.shape(),
+                right: (b.len(), 1),
+            }
+            .into());
+        }
+
+        let QrDecomposition { q, r } = self.qr()?;
+        let y = (&q.transpose() * b)?;
+
+        let n = self.column_count();
+        let scale = (0..n).map(|i| r[(i, i)].abs()).fold(0.0, f64::max);
+        let mut x = vec![0.0; n];
+
+        for i in (0..n).rev() {
+            if r[(i, i)].abs() <= f64::EPSILON * scale * n as f64 {
+                return Err(MatrixError::RankDeficient { column: i }.into());
+            }
+
+            let sum = ((i + 1)..n).map(|j| r[(i, j)] * x[j]).sum::<f64>();
+
+            x[i] = (y[i] - sum) / r[(i, i)];
+        }
+
+        Ok(x)
+    }
+
+    /// One-sided Jacobi singular value decomposition
+    pub fn svd(&self) -> Result<SingularValueDecomposition> {
+        if self.row_count() == 0 || self.column_count() == 0 {
+            return Err(MatrixError::Empty {
+                operation: "decompose",
+                shape: self.shape(),
+            }
+            .into());
+        }
+
+        if self.row_count() < self.column_count() {
+            let SingularValueDecomposition {
+                u,
+                singular_values,
+                v,
+            } = self.transpose().svd()?;
+
+            return Ok(SingularValueDecomposition {
+                u: v,
+                singular_values,
+                v: u,
+            });
+        }
+
+        let n = self.column_count();
+        let mut u = columns_of(self);
+        let mut v = columns_of(&Matrix::identity(n));
+
+        for _ in 0..JACOBI_SWEEP_LIMIT {
+            let mut rotated = false;
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let alpha = dot(&u[p], &u[p]);
+                    let beta = dot(&u[q], &u[q]);
+                    let gamma = dot(&u[p], &u[q]);
+
+                    if gamma.abs() <= JACOBI_TOLERANCE * (alpha * beta).sqrt() {
+                        continue;
+                    }
+
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta.powi(2)).sqrt());
+                    let c = 1.0 / (1.0 + t.powi(2)).sqrt();
+                    let s = c * t;
+
+                    rotate(&mut u, p, q, c, s);
+                    rotate(&mut v, p, q, c, s);
+                }
+            }
+
+            if !rotated {
+                break;
+            }
+        }
+
+        let mut order = (0..n)
+            .map(|j| (u[j].euclidean_norm(), j))
+            .collect::<Vec<_>>();
+        order.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let singular_values = order.iter().map(|(value, _)| *value).collect::<Vec<f64>>();
+        let u_columns = order
+            .iter()
+            .map(|(value, j)| {
+                u[*j]
+                    .iter()
+                    .map(|item| if *value > 0.0 { item / value } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+        let v_columns = order.iter().map(|(_, j)| v[*j].clone()).collect();
+
+        Ok(SingularValueDecomposition {
+            u: Matrix::from_rows(u_columns)?.transpose(),
+            singular_values,
+            v: Matrix::from_rows(v_columns)?.transpose(),
+        })
+    }
+}
+
+fn columns_of(matrix: &Matrix<f64>) -> Vec<Vec<f64>> {
+    matrix
+        .iter_columns()
+        .map(|column| column.iter().copied().collect())
+        .collect()
+}
+
+fn dot(left: &[f64], right: &[f64]) -> f64 {
+    left.iter().zip(right.iter()).map(|(a, b)| a * b).sum()
+}
+
+/// Applies the Householder reflection `I - 2vv^T` for a unit vector v
+fn reflect(reflector: &[f64], target: &mut [f64]) {
+    let projection = 2.0 * dot(reflector, target);
+
+    for (item, direction) in target.iter_mut().zip(reflector.iter()) {
+        *item -= projection * direction;
+    }
+}
+
+/// Applies the Jacobi rotation to columns p and q
+fn rotate(columns: &mut [Vec<f64>], p: usize, q: usize, c: f64, s: f64) {
+    for i in 0..columns[p].len() {
+        let left = columns[p][i];
+        let right = columns[q][i];
+
+        columns[p][i] = c * left - s * right;
+        columns[q][i] = s * left + c * right;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn sample() -> Matrix<f64> {
+        Matrix::from_rows(vec![
+            vec![2.0, -1.0, 0.5],
+            vec![1.0, 3.0, -2.0],
+            vec![0.0, 1.0, 4.0],
+            vec![-3.0, 0.5, 1.0],
+            vec![1.5, -2.0, 0.0],
+        ])
+        .unwrap()
+    }
+
+    fn assert_close(left: &Matrix<f64>, right: &Matrix<f64>) {
+        let difference = (left - right).unwrap().frobenius_norm();
+
+        assert!(difference < TOLERANCE, "difference {difference}");
+    }
+
+    #[test]
+    fn qr_reconstructs_the_matrix_with_orthonormal_q() {
+        let a = sample();
+        let QrDecomposition { q, r } = a.qr().unwrap();
+
+        assert_eq!(q.shape(), (5, 3));
+        assert_eq!(r.shape(), (3, 3));
+        assert_close(&(&q * &r).unwrap(), &a);
+        assert_close(&(&q.transpose() * &q).unwrap(), &Matrix::identity(3));
+
+        for i in 0..3 {
+            for j in 0..i {
+                assert!(r[(i, j)].abs() < TOLERANCE);
+            }
+        }
+    }
+
+    #[test]
+    fn least_squares_recovers_a_consistent_solution() {
+        let a = sample();
+        let expected = vec![1.0, -2.0, 0.5];
+        let b = (&a * expected.as_slice()).unwrap();
+
+        let solution = a.solve_least_squares(&b).unwrap();
+
+        assert!(solution.euclidean_distance(&expected) < TOLERANCE);
+    }
+
+    #[test]
+    fn least_squares_residual_is_orthogonal_to_the_columns() {
+        let a = sample();
+        let b = vec![1.0, 0.0, -1.0, 2.0, 0.5];
+
+        let solution = a.solve_least_squares(&b).unwrap();
+        let fitted = (&a * solution.as_slice()).unwrap();
+        let residual = b
+            .iter()
+            .zip(fitted.iter())
+            .map(|(b, fitted)| b - fitted)
+            .collect::<Vec<f64>>();
+
+        for column in a.iter_columns() {
+            let column = column.iter().copied().collect::<Vec<f64>>();
+
+            assert!(dot(&column, &residual).abs() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn least_squares_rejects_rank_deficient_matrices() {
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]).unwrap();
+
+        let error = a.solve_least_squares(&[1.0, 2.0, 3.0]).unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<MatrixError>(),
+            Some(MatrixError::RankDeficient { .. })
+        ));
+    }
+
+    #[test]
+    fn svd_reconstructs_the_matrix_with_descending_singular_values() {
+        for a in [sample(), sample().transpose()] {
+            let svd = a.svd().unwrap();
+
+            assert_close(&svd.truncated(3).unwrap(), &a);
+            assert!(svd
+                .singular_values
+                .windows(2)
+                .all(|pair| pair[0] >= pair[1]));
+            assert_close(
+                &(&svd.u.transpose() * &svd.u).unwrap(),
+                &Matrix::identity(3),
+            );
+            assert_close(
+                &(&svd.v.transpose() * &svd.v).unwrap(),
+                &Matrix::identity(3),
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_svd_error_is_the_next_singular_value() {
+        let a = sample();
+        let svd = a.svd().unwrap();
+
+        let error = (&a - &svd.truncated(1).unwrap()).unwrap().operator_norm();
+
+        assert!((error - svd.singular_values[1]).abs() < 1e-6);
+    }
+}
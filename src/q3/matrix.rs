@@ -1,105 +1,503 @@
 use std::{
     fmt::{Debug, Display},
-    iter::Sum,
-    ops::{Index, IndexMut, Mul},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Sub},
 };
 
-use anyhow::{Error, Result};
+use rand::Rng;
+use rayon::{
+    iter::{IndexedParallelIterator, ParallelIterator},
+    slice::{ParallelSlice, ParallelSliceMut},
+};
+use thiserror::Error;
+
+use crate::common::random;
 
-use crate::extensions::vec_extensions::{Single, TryCollect};
+/// Size of the square tiles used by the blocked matrix multiplication
+const BLOCK_SIZE: usize = 64;
 
+/// Iteration limit and relative tolerance of the power iteration behind [`Matrix::operator_norm`]
+const POWER_ITERATION_LIMIT: usize = 1000;
+const POWER_ITERATION_TOLERANCE: f64 = 1e-12;
+
+/// Shapes are reported as (rows, columns)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MatrixError {
+    #[error(
+        "cannot {operation} matrixes of mismatching dimensions [left={left:?} right={right:?}]"
+    )]
+    DimensionMismatch {
+        operation: &'static str,
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    #[error("cannot {operation} an empty matrix [shape={shape:?}]")]
+    Empty {
+        operation: &'static str,
+        shape: (usize, usize),
+    },
+    #[error("matrix is not a column vector [shape={shape:?}]")]
+    NotColumnVector { shape: (usize, usize) },
+    #[error("matrix index out of range [index={index:?} shape={shape:?}]")]
+    IndexOutOfRange {
+        index: (usize, usize),
+        shape: (usize, usize),
+    },
+    #[error("cannot {operation} a matrix with fewer rows than columns [shape={shape:?}]")]
+    Underdetermined {
+        operation: &'static str,
+        shape: (usize, usize),
+    },
+    #[error("sparse vector indices must be strictly ascending [position={position}]")]
+    UnsortedIndices { position: usize },
+    #[error("matrix is rank deficient [column={column}]")]
+    RankDeficient { column: usize },
+    #[error("all rows must have the same length [row={row} expected={expected} actual={actual}]")]
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Dense matrix with `m` rows and `n` columns stored contiguously in row-major order
 #[derive(Debug, Clone)]
 pub struct Matrix<TItem> {
     m: usize,
     n: usize,
-    inner: Vec<Vec<TItem>>,
+    inner: Vec<TItem>,
 }
 
 impl<TItem> Index<(usize, usize)> for Matrix<TItem> {
     type Output = TItem;
 
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.inner[index.0][index.1]
+        match self.get(index.0, index.1) {
+            Ok(item) => item,
+            Err(error) => panic!("{}", error),
+        }
     }
 }
 
 impl<TItem> IndexMut<(usize, usize)> for Matrix<TItem> {
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        &mut self.inner[index.0][index.1]
+        match self.get_mut(index.0, index.1) {
+            Ok(item) => item,
+            Err(error) => panic!("{}", error),
+        }
     }
 }
 
 impl<TItem: Default> Matrix<TItem> {
-    pub fn new(n: usize, m: usize) -> Self {
+    /// rows x columns matrix filled with the default item
+    pub fn new(rows: usize, columns: usize) -> Self {
         Self {
+            m: rows,
+            n: columns,
+            inner: (0..rows * columns).map(|_| TItem::default()).collect(),
+        }
+    }
+
+    pub fn zeros(rows: usize, columns: usize) -> Self {
+        Matrix::new(rows, columns)
+    }
+}
+
+impl<TItem: Default + From<u8>> Matrix<TItem> {
+    pub fn identity(size: usize) -> Self {
+        let mut matrix = Matrix::zeros(size, size);
+
+        for i in 0..size {
+            matrix[(i, i)] = TItem::from(1);
+        }
+
+        matrix
+    }
+}
+
+impl<TItem> Matrix<TItem> {
+    /// Builds a matrix from its rows, all rows must have the same length
+    pub fn from_rows(rows: Vec<Vec<TItem>>) -> Result<Self, MatrixError> {
+        let m = rows.len();
+        let n = rows.first().map_or(0, |row| row.len());
+
+        if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != n) {
+            return Err(MatrixError::RaggedRows {
+                row: i,
+                expected: n,
+                actual: row.len(),
+            });
+        }
+
+        Ok(Matrix {
             m,
             n,
-            inner: (0..m)
-                .map(|_| (0..n).map(|_| TItem::default()).collect())
+            inner: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.m
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.n
+    }
+
+    /// (rows, columns)
+    pub fn shape(&self) -> (usize, usize) {
+        (self.m, self.n)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Result<&TItem, MatrixError> {
+        let position = self.position(i, j)?;
+
+        Ok(&self.inner[position])
+    }
+
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Result<&mut TItem, MatrixError> {
+        let position = self.position(i, j)?;
+
+        Ok(&mut self.inner[position])
+    }
+
+    fn position(&self, i: usize, j: usize) -> Result<usize, MatrixError> {
+        if i < self.m && j < self.n {
+            Ok(i * self.n + j)
+        } else {
+            Err(MatrixError::IndexOutOfRange {
+                index: (i, j),
+                shape: self.shape(),
+            })
+        }
+    }
+
+    pub fn row(&self, i: usize) -> &[TItem] {
+        &self.inner[i * self.n..(i + 1) * self.n]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [TItem] {
+        &mut self.inner[i * self.n..(i + 1) * self.n]
+    }
+
+    pub fn column(&self, j: usize) -> MatrixColumn<'_, TItem> {
+        if j >= self.n {
+            panic!(
+                "{}",
+                MatrixError::IndexOutOfRange {
+                    index: (0, j),
+                    shape: self.shape(),
+                }
+            );
+        }
+
+        MatrixColumn {
+            matrix: self,
+            column: j,
+        }
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[TItem]> {
+        (0..self.m).map(move |i| self.row(i))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = MatrixColumn<'_, TItem>> {
+        (0..self.n).map(move |j| self.column(j))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = MatrixRow<'_, TItem>> {
+        // chunks_mut panics on a zero chunk size, a matrix without columns has no items anyway
+        self.inner
+            .chunks_mut(self.n.max(1))
+            .map(|row| MatrixRow { inner: row })
+    }
+}
+
+impl<TItem: Send> Matrix<TItem> {
+    pub fn par_iter_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [TItem]> {
+        self.inner.par_chunks_mut(self.n.max(1))
+    }
+}
+
+impl<TItem: Copy> Matrix<TItem> {
+    pub fn transpose(&self) -> Matrix<TItem> {
+        Matrix {
+            m: self.n,
+            n: self.m,
+            inner: (0..self.n)
+                .flat_map(|j| (0..self.m).map(move |i| self[(i, j)]))
                 .collect(),
         }
     }
+}
+
+impl Matrix<f64> {
+    pub fn frobenius_norm(&self) -> f64 {
+        self.inner
+            .iter()
+            .map(|item| item.powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = MatrixRow<TItem>> {
-        self.inner.iter_mut().map(|vec| MatrixRow { inner: vec })
+    /// Maximum absolute column sum, the operator norm induced by l1
+    pub fn one_norm(&self) -> f64 {
+        self.iter_columns()
+            .map(|column| column.iter().map(|item| item.abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Maximum absolute row sum, the operator norm induced by l-infinity
+    pub fn infinity_norm(&self) -> f64 {
+        self.iter_rows()
+            .map(|row| row.iter().map(|item| item.abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Largest singular value, the operator norm induced by l2, estimated by power iteration on `A^T A`
+    pub fn operator_norm(&self) -> f64 {
+        if self.inner.is_empty() {
+            return 0.0;
+        }
+
+        let mut rng = random::rng();
+        let mut vector = (0..self.n)
+            .map(|_| rng.gen_range(-1.0..1.0))
+            .collect::<Vec<f64>>();
+        let mut norm = 0.0;
+
+        for _ in 0..POWER_ITERATION_LIMIT {
+            let length = vector.iter().map(|item| item.powi(2)).sum::<f64>().sqrt();
+
+            if length == 0.0 {
+                return 0.0;
+            }
+
+            vector.iter_mut().for_each(|item| *item /= length);
+
+            let image = self
+                .iter_rows()
+                .map(|row| row.iter().zip(vector.iter()).map(|(a, b)| a * b).sum())
+                .collect::<Vec<f64>>();
+            let next_norm = image.iter().map(|item| item.powi(2)).sum::<f64>().sqrt();
+
+            vector = self
+                .iter_columns()
+                .map(|column| column.iter().zip(image.iter()).map(|(a, b)| a * b).sum())
+                .collect();
+
+            if (next_norm - norm).abs() <= POWER_ITERATION_TOLERANCE * next_norm {
+                return next_norm;
+            }
+
+            norm = next_norm;
+        }
+
+        norm
     }
 }
 
-impl<TItem: Default + Mul + Copy> Mul for Matrix<TItem>
+impl<'a, TItem> Mul<&'a Matrix<TItem>> for &'a Matrix<TItem>
 where
-    TItem::Output: Sum + Default,
+    TItem: Default + Mul<Output = TItem> + AddAssign + Copy + Send + Sync,
 {
-    type Output = Result<Matrix<TItem::Output>>;
+    type Output = Result<Matrix<TItem>, MatrixError>;
 
-    fn mul(self, rhs: Self) -> Self::Output {
+    fn mul(self, rhs: &'a Matrix<TItem>) -> Self::Output {
         if self.n != rhs.m {
-            Err(Error::msg(format!(
-                "cannot multiply matrixes [left.n={} right.m={}",
-                self.n, rhs.m
-            )))
-        } else {
-            let n = rhs.n;
-            let m = self.m;
-            let mut matrix = Matrix::new(n, m);
+            return Err(MatrixError::DimensionMismatch {
+                operation: "multiply",
+                left: self.shape(),
+                right: rhs.shape(),
+            });
+        }
+
+        let mut matrix = Matrix::new(self.m, rhs.n);
+
+        if matrix.inner.is_empty() {
+            return Ok(matrix);
+        }
+
+        // every task owns a band of BLOCK_SIZE output rows and walks the shared
+        // dimension and the output columns tile by tile to stay in cache
+        matrix
+            .inner
+            .par_chunks_mut(BLOCK_SIZE * rhs.n)
+            .enumerate()
+            .for_each(|(band, output)| {
+                let first_row = band * BLOCK_SIZE;
+                let rows = output.len() / rhs.n;
+
+                for inner_block in (0..self.n).step_by(BLOCK_SIZE) {
+                    let inner_end = (inner_block + BLOCK_SIZE).min(self.n);
 
-            for i in 0..m {
-                for j in 0..n {
-                    matrix[(i, j)] = (0..m).map(|index| self[(i, index)] * rhs[(index, j)]).sum();
+                    for column_block in (0..rhs.n).step_by(BLOCK_SIZE) {
+                        let column_end = (column_block + BLOCK_SIZE).min(rhs.n);
+
+                        for i in 0..rows {
+                            let output_row = &mut output[i * rhs.n..(i + 1) * rhs.n];
+
+                            for k in inner_block..inner_end {
+                                let left = self.inner[(first_row + i) * self.n + k];
+                                let right_row = &rhs.inner[k * rhs.n..(k + 1) * rhs.n];
+
+                                for j in column_block..column_end {
+                                    output_row[j] += left * right_row[j];
+                                }
+                            }
+                        }
+                    }
                 }
-            }
+            });
+
+        Ok(matrix)
+    }
+}
 
-            Ok(matrix)
+impl<'a, TItem> Mul<&'a [TItem]> for &'a Matrix<TItem>
+where
+    TItem: Default + Mul<Output = TItem> + AddAssign + Copy + Send + Sync,
+{
+    type Output = Result<Vec<TItem>, MatrixError>;
+
+    fn mul(self, rhs: &'a [TItem]) -> Self::Output {
+        if self.n != rhs.len() {
+            return Err(MatrixError::DimensionMismatch {
+                operation: "multiply",
+                left: self.shape(),
+                right: (rhs.len(), 1),
+            });
         }
+
+        if self.n == 0 {
+            return Ok((0..self.m).map(|_| TItem::default()).collect());
+        }
+
+        Ok(self
+            .inner
+            .par_chunks(self.n)
+            .map(|row| {
+                let mut sum = TItem::default();
+
+                for (left, right) in row.iter().zip(rhs.iter()) {
+                    sum += *left * *right;
+                }
+
+                sum
+            })
+            .collect())
     }
 }
 
-impl<TItem: Default + Mul + Copy> Mul<Vec<TItem>> for Matrix<TItem>
+impl<'a, TItem> Mul<&'a Vec<TItem>> for &'a Matrix<TItem>
 where
-    TItem::Output: Default + Sum + Copy,
+    TItem: Default + Mul<Output = TItem> + AddAssign + Copy + Send + Sync,
 {
-    type Output = Result<Vec<TItem::Output>>;
+    type Output = Result<Vec<TItem>, MatrixError>;
 
-    fn mul(self, rhs: Vec<TItem>) -> Self::Output {
-        (self * (Matrix::from(rhs)))?.try_into()
+    fn mul(self, rhs: &'a Vec<TItem>) -> Self::Output {
+        self * rhs.as_slice()
+    }
+}
+
+impl<TItem> Mul for Matrix<TItem>
+where
+    TItem: Default + Mul<Output = TItem> + AddAssign + Copy + Send + Sync,
+{
+    type Output = Result<Matrix<TItem>, MatrixError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
     }
 }
 
-impl<TItem: Mul + Copy> Mul<TItem> for Matrix<TItem>
+impl<TItem> Mul<Vec<TItem>> for Matrix<TItem>
 where
-    TItem::Output: Default,
+    TItem: Default + Mul<Output = TItem> + AddAssign + Copy + Send + Sync,
 {
+    type Output = Result<Vec<TItem>, MatrixError>;
+
+    fn mul(self, rhs: Vec<TItem>) -> Self::Output {
+        &self * rhs.as_slice()
+    }
+}
+
+impl<TItem: Mul + Copy> Mul<TItem> for &Matrix<TItem> {
     type Output = Matrix<TItem::Output>;
 
     fn mul(self, rhs: TItem) -> Self::Output {
-        let mut matrix = Matrix::new(self.n, self.m);
+        Matrix {
+            m: self.m,
+            n: self.n,
+            inner: self.inner.iter().map(|item| *item * rhs).collect(),
+        }
+    }
+}
 
-        for line in matrix.inner.iter_mut().zip(self.inner.iter()) {
-            for item in line.0.iter_mut().zip(line.1.iter()) {
-                *item.0 = rhs.clone() * *item.1;
-            }
+impl<TItem: Mul + Copy> Mul<TItem> for Matrix<TItem> {
+    type Output = Matrix<TItem::Output>;
+
+    fn mul(self, rhs: TItem) -> Self::Output {
+        &self * rhs
+    }
+}
+
+impl<'a, TItem: Add<Output = TItem> + Copy> Add<&'a Matrix<TItem>> for &'a Matrix<TItem> {
+    type Output = Result<Matrix<TItem>, MatrixError>;
+
+    fn add(self, rhs: &'a Matrix<TItem>) -> Self::Output {
+        self.zip_with(rhs, "add", |left, right| left + right)
+    }
+}
+
+impl<TItem: Add<Output = TItem> + Copy> Add for Matrix<TItem> {
+    type Output = Result<Matrix<TItem>, MatrixError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<'a, TItem: Sub<Output = TItem> + Copy> Sub<&'a Matrix<TItem>> for &'a Matrix<TItem> {
+    type Output = Result<Matrix<TItem>, MatrixError>;
+
+    fn sub(self, rhs: &'a Matrix<TItem>) -> Self::Output {
+        self.zip_with(rhs, "subtract", |left, right| left - right)
+    }
+}
+
+impl<TItem: Sub<Output = TItem> + Copy> Sub for Matrix<TItem> {
+    type Output = Result<Matrix<TItem>, MatrixError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<TItem: Copy> Matrix<TItem> {
+    fn zip_with<F: Fn(TItem, TItem) -> TItem>(
+        &self,
+        rhs: &Matrix<TItem>,
+        operation: &'static str,
+        f: F,
+    ) -> Result<Matrix<TItem>, MatrixError> {
+        if self.shape() != rhs.shape() {
+            return Err(MatrixError::DimensionMismatch {
+                operation,
+                left: self.shape(),
+                right: rhs.shape(),
+            });
         }
 
-        matrix
+        Ok(Matrix {
+            m: self.m,
+            n: self.n,
+            inner: self
+                .inner
+                .iter()
+                .zip(rhs.inner.iter())
+                .map(|(left, right)| f(*left, *right))
+                .collect(),
+        })
     }
 }
 
@@ -118,7 +516,7 @@ impl<TItem: Display> Display for Matrix<TItem> {
             .iter()
             .map(|line| line.len())
             .max()
-            .ok_or(std::fmt::Error::default())?))
+            .ok_or(std::fmt::Error)?))
             .map(|_| "-".into())
             .collect::<Vec<String>>()
             .join("");
@@ -137,21 +535,27 @@ impl<TItem> From<Vec<TItem>> for Matrix<TItem> {
         Matrix {
             m: source.len(),
             n: 1,
-            inner: source.into_iter().map(|item| vec![item]).collect(),
+            inner: source,
         }
     }
 }
 
-impl<TItem: Copy> TryInto<Vec<TItem>> for Matrix<TItem> {
-    type Error = Error;
+impl<TItem> TryInto<Vec<TItem>> for Matrix<TItem> {
+    type Error = MatrixError;
 
     fn try_into(self) -> Result<Vec<TItem>, Self::Error> {
-        self.inner.into_iter().map(|vec| vec.single()).try_collect()
+        if self.n == 1 {
+            Ok(self.inner)
+        } else {
+            Err(MatrixError::NotColumnVector {
+                shape: self.shape(),
+            })
+        }
     }
 }
 
 pub struct MatrixRow<'a, TItem> {
-    inner: &'a mut Vec<TItem>,
+    inner: &'a mut [TItem],
 }
 
 impl<'a, TItem> MatrixRow<'a, TItem> {
@@ -159,3 +563,163 @@ impl<'a, TItem> MatrixRow<'a, TItem> {
         self.inner.iter_mut()
     }
 }
+
+pub struct MatrixColumn<'a, TItem> {
+    matrix: &'a Matrix<TItem>,
+    column: usize,
+}
+
+impl<'a, TItem> MatrixColumn<'a, TItem> {
+    pub fn len(&self) -> usize {
+        self.matrix.m
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matrix.m == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a TItem> {
+        let matrix = self.matrix;
+        let column = self.column;
+
+        (0..matrix.m).map(move |i| &matrix[(i, column)])
+    }
+}
+
+impl<'a, TItem> Index<usize> for MatrixColumn<'a, TItem> {
+    type Output = TItem;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.matrix[(index, self.column)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// rows x columns matrix of small integers, products of them are exact in f64
+    fn sample(rows: usize, columns: usize) -> Matrix<f64> {
+        Matrix::from_rows(
+            (0..rows)
+                .map(|i| {
+                    (0..columns)
+                        .map(|j| ((i * 7 + j * 3) % 11) as f64 - 5.0)
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn naive_product(left: &Matrix<f64>, right: &Matrix<f64>) -> Matrix<f64> {
+        let mut product = Matrix::zeros(left.row_count(), right.column_count());
+
+        for i in 0..left.row_count() {
+            for j in 0..right.column_count() {
+                product[(i, j)] = (0..left.column_count())
+                    .map(|k| left[(i, k)] * right[(k, j)])
+                    .sum();
+            }
+        }
+
+        product
+    }
+
+    #[test]
+    fn multiply_matches_naive_product_across_partial_blocks() {
+        for (m, k, n) in [
+            (1, 1, 1),
+            (3, 5, 2),
+            (70, 130, 67),
+            (BLOCK_SIZE + 1, BLOCK_SIZE, 2),
+        ] {
+            let (left, right) = (sample(m, k), sample(k, n));
+            let product = (&left * &right).unwrap();
+
+            assert_eq!(product.shape(), (m, n));
+            assert_eq!(product.inner, naive_product(&left, &right).inner);
+        }
+    }
+
+    #[test]
+    fn multiply_by_vector_matches_naive_product() {
+        let matrix = sample(67, 131);
+        let vector = (0..131).map(|j| (j % 5) as f64 - 2.0).collect::<Vec<f64>>();
+
+        let product = (&matrix * &vector).unwrap();
+        let expected = naive_product(&matrix, &Matrix::from(vector.clone()));
+
+        assert_eq!(product, expected.inner);
+    }
+
+    #[test]
+    fn multiply_rejects_mismatching_dimensions() {
+        assert!(matches!(
+            &sample(2, 3) * &sample(2, 3),
+            Err(MatrixError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            &sample(2, 3) * &vec![1.0; 2],
+            Err(MatrixError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn multiply_handles_empty_shared_dimension() {
+        let product = (&Matrix::<f64>::zeros(3, 0) * &Matrix::zeros(0, 4)).unwrap();
+
+        assert_eq!(product.shape(), (3, 4));
+        assert!(product.inner.iter().all(|item| *item == 0.0));
+    }
+
+    #[test]
+    fn transpose_swaps_indices() {
+        let matrix = sample(3, 5);
+        let transposed = matrix.transpose();
+
+        assert_eq!(transposed.shape(), (5, 3));
+
+        for i in 0..3 {
+            for j in 0..5 {
+                assert_eq!(transposed[(j, i)], matrix[(i, j)]);
+            }
+        }
+
+        assert_eq!(transposed.transpose().inner, matrix.inner);
+    }
+
+    #[test]
+    fn add_and_subtract_are_itemwise() {
+        let (left, right) = (sample(4, 3), sample(3, 4).transpose());
+
+        let sum = (&left + &right).unwrap();
+        let difference = (&sum - &right).unwrap();
+
+        for i in 0..4 {
+            for j in 0..3 {
+                assert_eq!(sum[(i, j)], left[(i, j)] + right[(i, j)]);
+            }
+        }
+
+        assert_eq!(difference.inner, left.inner);
+        assert!(matches!(
+            &left + &sample(3, 4),
+            Err(MatrixError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn norms_match_their_definitions() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, -2.0], vec![-3.0, 4.0]]).unwrap();
+
+        assert!((matrix.frobenius_norm() - 30_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(matrix.one_norm(), 6.0);
+        assert_eq!(matrix.infinity_norm(), 7.0);
+
+        // the largest singular value of [[1, -2], [-3, 4]] is sqrt(15 + sqrt(221))
+        let largest_singular_value = (15.0 + 221_f64.sqrt()).sqrt();
+        assert!((matrix.operator_norm() - largest_singular_value).abs() < 1e-9);
+        assert_eq!(Matrix::<f64>::zeros(2, 3).operator_norm(), 0.0);
+    }
+}
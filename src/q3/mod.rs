@@ -1,20 +1,43 @@
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use clap::Args;
-use log::warn;
-use rand_distr::{Distribution, Normal};
+use clap::{Args, Subcommand};
+use log::debug;
+use nameof::name_of;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
-        algorithm::Algorithm, reduce::IntoReduce, repeat::IntoRepeat, with_name::IntoWithName,
+        algorithm::Algorithm,
+        map::IntoMap,
+        random,
+        reduce::IntoReduce,
+        registry::Registry,
+        repeat::IntoRepeat,
+        run_report::RunReport,
+        stopping,
+        tolerate_failures::Failures,
+        validation::{validate_count, value_range},
+        with_name::IntoWithName,
     },
+    error::CliError,
     extensions::vec_extensions::{L2NormVecExtension, SampleUniformVecExtensions},
 };
 
-use self::matrix::Matrix;
+use self::{dataset::Q3DatasetCommand, operator::LinearOperator, projection::ProjectionKind};
 
-mod matrix;
+mod dataset;
+pub mod decomposition;
+pub mod matrix;
+pub mod operator;
+pub mod projection;
+pub mod random_matrix;
+mod seeded_projection;
+pub mod serialization;
+pub mod sparse_matrix;
 
-#[derive(Debug, Args)]
+#[derive(Debug, Args, Serialize, Deserialize)]
 pub struct Q3Command {
     ///epsilon value
     #[clap(short, long, default_value = "0.5")]
@@ -22,59 +45,207 @@ pub struct Q3Command {
     ///delta value
     #[clap(short, long, default_value = "0.01")]
     delta: f64,
+    ///Length of the array that will be generated
+    #[clap(short, long, default_value = "1000")]
+    array_length: usize,
+    ///Smallest value of the generated array and point items
+    #[clap(long, default_value = "0")]
+    min_value: i32,
+    ///Largest value of the generated array and point items
+    #[clap(long, default_value = "2")]
+    max_value: i32,
+    ///Amount of times the algorithm will run
+    #[clap(short, long, default_value = "1000")]
+    repeat_count: usize,
+    ///Kind of random projection matrix to sample
+    #[clap(short, long, arg_enum, default_value = "gaussian")]
+    projection: ProjectionKind,
+    ///Regenerate the projection entries from a seed instead of storing the matrix
+    #[clap(long, conflicts_with = "sparse")]
+    matrix_free: bool,
+    ///Store the projection as a sparse matrix
+    #[clap(long)]
+    sparse: bool,
+    ///Stop repeating once at least 30 repetitions completed and the 95% confidence interval of the hit rate is narrower than this width
+    #[clap(long)]
+    target_width: Option<f64>,
+    ///Keep running when repetitions fail and report the failures instead of failing on the first one
+    #[clap(long)]
+    tolerate_failures: bool,
+    #[clap(subcommand)]
+    #[serde(skip_deserializing)]
+    command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand, Serialize)]
+enum Commands {
+    ///Projects a set of points and checks the pairwise distance distortion
+    Dataset(Q3DatasetCommand),
+}
+
+/// Target dimension `k` for which a single vector norm is preserved within `(1±epsilon)` with probability `1-delta`
+fn projection_dimension(epsilon: f64, delta: f64) -> usize {
+    (21.0 * (1.0 / delta).ln() / epsilon.powi(2)).ceil() as usize
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register("q3", "Run Question3 program", Q3Command::algorithm);
 }
 
 impl Q3Command {
-    pub fn invoke(&self) -> Result<()> {
-        let array : Vec<f64> = Vec::with_random_items_in_range(1000, || 0..=2)
+    pub fn invoke(&self) -> Result<RunReport> {
+        match &self.command {
+            Some(Commands::Dataset(command)) => {
+                self.validate()?;
+
+                command.invoke(
+                    self.epsilon,
+                    self.delta,
+                    self.projection,
+                    self.sparse,
+                    value_range(self.min_value, self.max_value)?,
+                )
+            }
+            None => self.algorithm()?.run(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(self.epsilon > 0.0 && self.epsilon < 1.0) {
+            return Err(CliError::invalid_parameter(
+                name_of!(epsilon in Self),
+                self.epsilon,
+                "must be between 0 and 1",
+            )
+            .into());
+        }
+
+        if !(self.delta > 0.0 && self.delta < 1.0) {
+            return Err(CliError::invalid_parameter(
+                name_of!(delta in Self),
+                self.delta,
+                "must be between 0 and 1",
+            )
+            .into());
+        }
+
+        // experiment files and --set bypass the clap conflict between the two flags
+        if self.matrix_free && self.sparse {
+            return Err(CliError::invalid_parameter(
+                name_of!(matrix_free in Self),
+                self.matrix_free,
+                "cannot be combined with sparse",
+            )
+            .into());
+        }
+
+        validate_count(name_of!(array_length in Self), self.array_length)?;
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+        stopping::validate_target_width(self.target_width)
+    }
+
+    pub fn algorithm(
+        &self,
+    ) -> Result<impl Algorithm<Output = impl Serialize> + Sync + Send + 'static> {
+        let estimator = self.estimator()?;
+
+        let l2_norm = estimator.input.array.l2_norm();
+        let low_bar = (1.0 - self.epsilon) * l2_norm;
+        let high_bar = (1.0 + self.epsilon) * l2_norm;
+
+        Ok(estimator
+            .repeat(self.repeat_count)
+            .stop_early(self.target_width, move |estimate| {
+                estimate.l2_norm >= low_bar && estimate.l2_norm <= high_bar
+            })
+            .tolerate_failures(self.tolerate_failures)
+            .reduce(move |mut repetitions, (input, _)| {
+                repetitions.require_outputs()?;
+
+                let series = repetitions.outputs;
+                let hit_count = series
+                    .iter()
+                    .filter(|estimate| estimate.l2_norm >= low_bar && estimate.l2_norm <= high_bar)
+                    .count();
+
+                let hit_percent = (hit_count as f64 / series.len() as f64) * 100.0;
+                let construction_time = series
+                    .iter()
+                    .map(|estimate| estimate.construction_time)
+                    .sum::<Duration>();
+                let multiplication_time = series
+                    .iter()
+                    .map(|estimate| estimate.multiplication_time)
+                    .sum::<Duration>();
+
+                Ok(Q3AlgorithmResult {
+                    projection: input.projection,
+                    hit_percent,
+                    average_construction_time: construction_time / series.len() as u32,
+                    average_multiplication_time: multiplication_time / series.len() as u32,
+                    failures: repetitions.failures,
+                })
+            })
+            .with_name("Q3 Algorithm".into()))
+    }
+
+    /// Every single estimate along with the L2 norm of the generated array
+    pub fn estimates(&self) -> Result<(Vec<f64>, f64)> {
+        let estimator = self.estimator()?;
+        let l2_norm = estimator.input.array.l2_norm();
+
+        let estimates = estimator
+            .repeat(self.repeat_count)
+            .map(|estimates| {
+                estimates
+                    .iter()
+                    .map(|estimate| estimate.l2_norm)
+                    .collect::<Vec<f64>>()
+            })
+            .with_name("Q3 estimates".into())
+            .run_output()?;
+
+        Ok((estimates, l2_norm))
+    }
+
+    fn estimator(&self) -> Result<JonsonLindenshtrassAlgorithm> {
+        self.validate()?;
+
+        let values = value_range(self.min_value, self.max_value)?;
+        let array: Vec<f64> = Vec::with_random_items_in_range(self.array_length, || values.clone())
             .into_iter()
             .map(|num: i32| num as f64)
             .collect();
 
-        JonsonLindenshtrassAlgorithm {
+        Ok(JonsonLindenshtrassAlgorithm {
             input: JonsonLindenshtrassAlgorithmInput {
-                n: 1000,
+                array,
                 epsilon: self.epsilon,
                 delta: self.delta,
+                projection: self.projection,
+                matrix_free: self.matrix_free,
+                sparse: self.sparse,
             },
-        }
-        .repeat(1000)
-        .reduce(|series| {
-            let l2_norm = array.l2_norm();
-            let low_bar = (1.0 - self.epsilon) * l2_norm;
-            let high_bar = (1.0 + self.epsilon) * l2_norm;
-
-            let hit_count = series
-                .iter()
-                .filter(|matrix| match (*matrix).clone() * array.clone() {
-                    Ok(vec) => {
-                        let estimated_l2_norm = vec.l2_norm();
-
-                        estimated_l2_norm >= low_bar && estimated_l2_norm <= high_bar
-                    }
-                    Err(err) => {
-                        warn!("{}", err);
-                        false
-                    }
-                })
-                .count();
-
-            let hit_percent = (hit_count as f64 / series.len() as f64) * 100.0;
-
-            Ok(Q3AlgorithmResult {
-                _hit_percent: hit_percent,
-            })
         })
-        .with_name("Q3 Algorithm".into())
-        .run()
     }
 }
 
 #[derive(Debug, Clone)]
 struct JonsonLindenshtrassAlgorithmInput {
-    n: usize,
+    array: Vec<f64>,
     epsilon: f64,
     delta: f64,
+    projection: ProjectionKind,
+    matrix_free: bool,
+    sparse: bool,
+}
+
+/// Estimated l2 norm of the input array under a single sampled projection
+#[derive(Debug)]
+struct JonsonLindenshtrassEstimate {
+    l2_norm: f64,
+    construction_time: Duration,
+    multiplication_time: Duration,
 }
 
 struct JonsonLindenshtrassAlgorithm {
@@ -83,7 +254,7 @@ struct JonsonLindenshtrassAlgorithm {
 
 impl Algorithm for JonsonLindenshtrassAlgorithm {
     type Input = JonsonLindenshtrassAlgorithmInput;
-    type Output = Matrix<f64>;
+    type Output = JonsonLindenshtrassEstimate;
 
     fn name(&self) -> String {
         "Jonson Lindenshtrass Algorithm".into()
@@ -93,27 +264,71 @@ impl Algorithm for JonsonLindenshtrassAlgorithm {
         self.input.clone()
     }
 
-    fn run_internal<F: Fn() + Send + Sync>(&self, update_progress: F) -> Result<Matrix<f64>> {
-        let k = 21.0 * ((1.0 / self.input.delta).ln()) / self.input.epsilon.powi(2);
+    fn run_internal<F: Fn() + Send + Sync>(
+        &self,
+        update_progress: F,
+    ) -> Result<JonsonLindenshtrassEstimate> {
+        debug!("run_internal started");
+
+        let k = projection_dimension(self.input.epsilon, self.input.delta);
+
+        let (l2_norm, construction_time, multiplication_time) = if self.input.matrix_free {
+            let start = Instant::now();
+            let projection = self.input.projection.seeded(k, random::rng().gen())?;
+            let construction_time = start.elapsed();
+
+            let start = Instant::now();
+            let l2_norm = projection.apply(self.input.array.iter().copied()).l2_norm();
 
-        let mut matrix = Matrix::new(self.input.n, k.ceil() as usize);
+            (l2_norm, construction_time, start.elapsed())
+        } else {
+            let (n, rng) = (self.input.array.len(), &mut random::rng());
 
-        let distribution = Normal::new(0.0, 1.0)?;
-        let mut rng = rand::thread_rng();
+            let start = Instant::now();
+            let operator: Box<dyn LinearOperator> = if self.input.sparse {
+                Box::new(self.input.projection.generate_sparse(k, n, rng)?)
+            } else {
+                Box::new(self.input.projection.generate(k, n, rng)?)
+            };
+            let construction_time = start.elapsed();
 
-        for mut row in matrix.iter_mut() {
-            for item in row.iter_mut() {
-                *item = distribution.sample(&mut rng);
+            let start = Instant::now();
+            let l2_norm = if self.input.sparse {
+                let (indices, values): (Vec<usize>, Vec<f64>) = self
+                    .input
+                    .array
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .filter(|(_, item)| *item != 0.0)
+                    .unzip();
+
+                operator.multiply_sparse(&indices, &values)?
+            } else {
+                operator.multiply(&self.input.array)?
             }
-        }
+            .l2_norm();
+
+            (l2_norm, construction_time, start.elapsed())
+        };
+
+        debug!("run_internal finished [{}={}]", name_of!(l2_norm), l2_norm);
 
         update_progress();
 
-        Ok(matrix * (1.0 / k.sqrt()))
+        Ok(JonsonLindenshtrassEstimate {
+            l2_norm,
+            construction_time,
+            multiplication_time,
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct Q3AlgorithmResult {
-    _hit_percent: f64,
+    projection: ProjectionKind,
+    hit_percent: f64,
+    average_construction_time: Duration,
+    average_multiplication_time: Duration,
+    failures: Failures,
 }
@@ -0,0 +1,119 @@
+use anyhow::Result;
+
+use super::matrix::{Matrix, MatrixError};
+
+/// Linear map `x -> Ax` shared by the dense and sparse matrix types
+pub trait LinearOperator: Send + Sync {
+    fn row_count(&self) -> usize;
+    fn column_count(&self) -> usize;
+
+    /// `Ax` for a dense vector x
+    fn multiply(&self, vector: &[f64]) -> Result<Vec<f64>>;
+
+    /// `A^T x` for a dense vector x
+    fn transpose_multiply(&self, vector: &[f64]) -> Result<Vec<f64>>;
+
+    /// `Ax` for a sparse vector x given by its non zero indices (ascending) and values
+    fn multiply_sparse(&self, indices: &[usize], values: &[f64]) -> Result<Vec<f64>>;
+
+    /// `AX` for a dense matrix X, one product per column
+    fn multiply_matrix(&self, matrix: &Matrix<f64>) -> Result<Matrix<f64>> {
+        let columns = matrix
+            .iter_columns()
+            .map(|column| self.multiply(&column.iter().copied().collect::<Vec<f64>>()))
+            .collect::<Result<Vec<Vec<f64>>>>()?;
+
+        if columns.is_empty() {
+            return Ok(Matrix::zeros(self.row_count(), 0));
+        }
+
+        Ok(Matrix::from_rows(columns)?.transpose())
+    }
+}
+
+/// Checks that a vector of the given length can be multiplied by an operator of the given (rows, columns) shape
+pub(super) fn check_length(shape: (usize, usize), length: usize) -> Result<(), MatrixError> {
+    if shape.1 == length {
+        Ok(())
+    } else {
+        Err(MatrixError::DimensionMismatch {
+            operation: "multiply",
+            left: shape,
+            right: (length, 1),
+        })
+    }
+}
+
+pub(super) fn check_sparse_vector(
+    length: usize,
+    indices: &[usize],
+    values: &[f64],
+) -> Result<(), MatrixError> {
+    if indices.len() != values.len() {
+        return Err(MatrixError::DimensionMismatch {
+            operation: "pair sparse vector indices with values",
+            left: (indices.len(), 1),
+            right: (values.len(), 1),
+        });
+    }
+
+    if let Some(position) = indices.windows(2).position(|pair| pair[0] >= pair[1]) {
+        return Err(MatrixError::UnsortedIndices {
+            position: position + 1,
+        });
+    }
+
+    match indices.last() {
+        Some(index) if *index >= length => Err(MatrixError::IndexOutOfRange {
+            index: (*index, 0),
+            shape: (length, 1),
+        }),
+        _ => Ok(()),
+    }
+}
+
+impl LinearOperator for Matrix<f64> {
+    fn row_count(&self) -> usize {
+        Matrix::row_count(self)
+    }
+
+    fn column_count(&self) -> usize {
+        Matrix::column_count(self)
+    }
+
+    fn multiply(&self, vector: &[f64]) -> Result<Vec<f64>> {
+        Ok((self * vector)?)
+    }
+
+    fn transpose_multiply(&self, vector: &[f64]) -> Result<Vec<f64>> {
+        check_length(
+            (Matrix::column_count(self), Matrix::row_count(self)),
+            vector.len(),
+        )?;
+
+        let mut result = vec![0.0; Matrix::column_count(self)];
+
+        for (row, scale) in self.iter_rows().zip(vector.iter()) {
+            for (item, value) in result.iter_mut().zip(row.iter()) {
+                *item += value * scale;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn multiply_sparse(&self, indices: &[usize], values: &[f64]) -> Result<Vec<f64>> {
+        check_sparse_vector(Matrix::column_count(self), indices, values)?;
+
+        Ok(self
+            .iter_rows()
+            .map(|row| {
+                indices
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(j, value)| row[*j] * value)
+                    .sum()
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::{Error, Result};
+use clap::ArgEnum;
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    matrix::Matrix,
+    random_matrix::{RandomSign, SparseSign},
+    seeded_projection::{SeededEntries, SeededProjection},
+    sparse_matrix::{SparseLayout, SparseMatrix},
+};
+
+#[derive(Debug, Clone, Copy, ArgEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectionKind {
+    ///Dense matrix with N(0, 1) entries
+    Gaussian,
+    ///Dense matrix with uniform ±1 entries (Achlioptas)
+    Achlioptas,
+    ///Matrix with entries √3·{+1, 0, -1} with probabilities {1/6, 2/3, 1/6} (Achlioptas)
+    Sparse,
+    ///Matrix with entries √s·{+1, 0, -1} with probabilities {1/2s, 1-1/s, 1/2s}, s = √n (Li et al.)
+    VerySparse,
+    ///Sparse Gaussian matrix applied after a randomized Hadamard transform (Ailon-Chazelle)
+    FastJl,
+    ///Single ±1 entry per column in a uniformly chosen row (CountSketch)
+    CountSketch,
+}
+
+impl ProjectionKind {
+    /// Generates a k x n projection matrix `A` normalized so that `E[|Ax|^2] = |x|^2`
+    pub fn generate<R: Rng>(&self, k: usize, n: usize, rng: &mut R) -> Result<Matrix<f64>> {
+        let scale = 1.0 / (k as f64).sqrt();
+
+        let matrix = match self {
+            ProjectionKind::Gaussian => Matrix::random(k, n, &Normal::new(0.0, scale)?, rng),
+            ProjectionKind::Achlioptas => Matrix::random_sign(k, n, rng) * scale,
+            ProjectionKind::Sparse => Matrix::random(k, n, &SparseSign::new(3.0)?, rng) * scale,
+            ProjectionKind::VerySparse => {
+                let s = (n as f64).sqrt().max(1.0);
+
+                Matrix::random(k, n, &SparseSign::new(s)?, rng) * scale
+            }
+            ProjectionKind::FastJl => {
+                let d = n.next_power_of_two();
+                let q = ((n as f64).ln().powi(2) / d as f64).min(1.0);
+                let distribution = Normal::new(0.0, 1.0 / q.sqrt())?;
+
+                // only the first n columns of HD act on the zero padded vector
+                let hadamard = Matrix::random_hadamard(d, rng)?;
+                let mut matrix = Matrix::zeros(k, n);
+
+                for i in 0..k {
+                    for l in 0..d {
+                        if rng.gen_bool(q) {
+                            let value = distribution.sample(rng) * scale;
+
+                            for (item, entry) in matrix.row_mut(i).iter_mut().zip(hadamard.row(l)) {
+                                *item += value * entry;
+                            }
+                        }
+                    }
+                }
+
+                matrix
+            }
+            ProjectionKind::CountSketch => {
+                let mut matrix = Matrix::zeros(k, n);
+
+                for j in 0..n {
+                    let i = rng.gen_range(0..k);
+
+                    matrix[(i, j)] = RandomSign.sample(rng);
+                }
+
+                matrix
+            }
+        };
+
+        Ok(matrix)
+    }
+
+    /// Generates the k x n projection in CSC layout, sparse kinds never materialize their zeros
+    pub fn generate_sparse<R: Rng>(&self, k: usize, n: usize, rng: &mut R) -> Result<SparseMatrix> {
+        let scale = 1.0 / (k as f64).sqrt();
+        let mut triplets = Vec::new();
+
+        match self {
+            ProjectionKind::Sparse | ProjectionKind::VerySparse => {
+                let distribution = SparseSign::new(match self {
+                    ProjectionKind::Sparse => 3.0,
+                    _ => (n as f64).sqrt().max(1.0),
+                })?;
+
+                for j in 0..n {
+                    for i in 0..k {
+                        let value = distribution.sample(rng);
+
+                        if value != 0.0 {
+                            triplets.push((i, j, value * scale));
+                        }
+                    }
+                }
+            }
+            ProjectionKind::CountSketch => {
+                for j in 0..n {
+                    triplets.push((rng.gen_range(0..k), j, RandomSign.sample(rng)));
+                }
+            }
+            _ => return SparseMatrix::from_dense(&self.generate(k, n, rng)?, SparseLayout::Csc),
+        }
+
+        SparseMatrix::from_triplets(k, n, triplets, SparseLayout::Csc)
+    }
+
+    /// Matrix-free counterpart of the k x n projection, only available for kinds with i.i.d. entries
+    pub fn seeded(&self, k: usize, seed: u64) -> Result<SeededProjection> {
+        match self {
+            ProjectionKind::Gaussian => Ok(SeededProjection::new(seed, k, SeededEntries::Gaussian)),
+            ProjectionKind::Achlioptas => Ok(SeededProjection::new(seed, k, SeededEntries::Sign)),
+            _ => Err(Error::msg(format!(
+                "projection kind has no matrix free implementation [projection={:?}]",
+                self
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::random;
+
+    fn entries(kind: ProjectionKind, seed: u64) -> Vec<f64> {
+        let projection =
+            random::scoped(Some(seed), || kind.generate(4, 16, &mut random::rng())).unwrap();
+
+        projection.iter_rows().flatten().copied().collect()
+    }
+
+    #[test]
+    fn seeded_projections_are_reproducible() {
+        for kind in ProjectionKind::value_variants().iter().copied() {
+            assert_eq!(entries(kind, 11), entries(kind, 11), "{:?}", kind);
+            assert_ne!(entries(kind, 11), entries(kind, 12), "{:?}", kind);
+        }
+    }
+}
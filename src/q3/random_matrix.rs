@@ -0,0 +1,259 @@
+use anyhow::{Error, Result};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+use super::{decomposition::QrDecomposition, matrix::Matrix};
+
+impl Matrix<f64> {
+    /// Generates a rows x columns matrix with i.i.d. entries sampled from the distribution,
+    /// every row is filled in parallel by its own generator seeded from rng
+    pub fn random<D: Distribution<f64> + Sync, R: Rng>(
+        rows: usize,
+        columns: usize,
+        distribution: &D,
+        rng: &mut R,
+    ) -> Self {
+        let seeds = (0..rows).map(|_| rng.gen()).collect::<Vec<u64>>();
+        let mut matrix = Matrix::zeros(rows, columns);
+
+        matrix
+            .par_iter_rows_mut()
+            .zip(seeds)
+            .for_each(|(row, seed)| {
+                let mut rng = StdRng::seed_from_u64(seed);
+
+                for item in row.iter_mut() {
+                    *item = distribution.sample(&mut rng);
+                }
+            });
+
+        matrix
+    }
+
+    /// Generates a rows x columns matrix with i.i.d. N(0, standard_deviation^2) entries
+    pub fn random_gaussian<R: Rng>(
+        rows: usize,
+        columns: usize,
+        standard_deviation: f64,
+        rng: &mut R,
+    ) -> Result<Self> {
+        Ok(Matrix::random(
+            rows,
+            columns,
+            &Normal::new(0.0, standard_deviation)?,
+            rng,
+        ))
+    }
+
+    /// Generates a rows x columns matrix with i.i.d. uniform ±1 entries
+    pub fn random_sign<R: Rng>(rows: usize, columns: usize, rng: &mut R) -> Self {
+        Matrix::random(rows, columns, &RandomSign, rng)
+    }
+
+    /// Generates a rows x columns matrix with orthonormal columns distributed uniformly
+    /// (Haar measure), from the QR decomposition of a gaussian matrix
+    pub fn random_orthogonal<R: Rng>(rows: usize, columns: usize, rng: &mut R) -> Result<Self> {
+        if rows < columns {
+            return Err(Error::msg(format!(
+                "orthonormal columns require at least as many rows as columns [rows={} columns={}]",
+                rows, columns
+            )));
+        }
+
+        let QrDecomposition { mut q, r } =
+            Matrix::random_gaussian(rows, columns, 1.0, rng)?.qr()?;
+
+        // QR is only unique up to the signs of the diagonal of R, fixing them makes Q uniform
+        let signs = (0..columns)
+            .map(|j| if r[(j, j)] < 0.0 { -1.0 } else { 1.0 })
+            .collect::<Vec<f64>>();
+
+        q.par_iter_rows_mut().for_each(|row| {
+            for (item, sign) in row.iter_mut().zip(signs.iter()) {
+                *item *= sign;
+            }
+        });
+
+        Ok(q)
+    }
+
+    /// Generates a uniformly chosen size x size permutation matrix
+    pub fn random_permutation<R: Rng>(size: usize, rng: &mut R) -> Self {
+        let mut permutation = (0..size).collect::<Vec<usize>>();
+        permutation.shuffle(rng);
+
+        let mut matrix = Matrix::zeros(size, size);
+
+        for (i, j) in permutation.into_iter().enumerate() {
+            matrix[(i, j)] = 1.0;
+        }
+
+        matrix
+    }
+
+    /// Orthonormal size x size Sylvester-Hadamard matrix, size must be a power of two
+    pub fn hadamard(size: usize) -> Result<Self> {
+        if !size.is_power_of_two() {
+            return Err(Error::msg(format!(
+                "hadamard matrix size must be a power of two [size={}]",
+                size
+            )));
+        }
+
+        let scale = 1.0 / (size as f64).sqrt();
+        let mut matrix = Matrix::zeros(size, size);
+
+        matrix.par_iter_rows_mut().enumerate().for_each(|(i, row)| {
+            for (j, item) in row.iter_mut().enumerate() {
+                *item = hadamard_sign(i, j) * scale;
+            }
+        });
+
+        Ok(matrix)
+    }
+
+    /// Randomized Hadamard transform `HD` with a uniform ±1 diagonal D, an orthogonal matrix
+    /// that spreads the mass of every vector over all coordinates
+    pub fn random_hadamard<R: Rng>(size: usize, rng: &mut R) -> Result<Self> {
+        let signs = (0..size)
+            .map(|_| RandomSign.sample(rng))
+            .collect::<Vec<f64>>();
+        let mut matrix = Matrix::hadamard(size)?;
+
+        matrix.par_iter_rows_mut().for_each(|row| {
+            for (item, sign) in row.iter_mut().zip(signs.iter()) {
+                *item *= sign;
+            }
+        });
+
+        Ok(matrix)
+    }
+}
+
+/// Uniform distribution over {+1, -1}
+#[derive(Debug, Clone, Copy)]
+pub struct RandomSign;
+
+impl Distribution<f64> for RandomSign {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if rng.gen_bool(0.5) {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+/// √s·{+1, 0, -1} with probabilities {1/2s, 1-1/s, 1/2s}
+#[derive(Debug, Clone, Copy)]
+pub struct SparseSign {
+    s: f64,
+}
+
+impl SparseSign {
+    /// The probabilities are only valid for s >= 1
+    pub fn new(s: f64) -> Result<Self> {
+        if !(s >= 1.0 && s.is_finite()) {
+            return Err(Error::msg(format!(
+                "sparse sign sparsity must be a finite number of at least 1 [s={}]",
+                s
+            )));
+        }
+
+        Ok(SparseSign { s })
+    }
+}
+
+impl Distribution<f64> for SparseSign {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if rng.gen_bool(1.0 / self.s) {
+            RandomSign.sample(rng) * self.s.sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Sign of the (i, j) entry of the unnormalized Sylvester-Hadamard matrix
+pub fn hadamard_sign(i: usize, j: usize) -> f64 {
+    if (i & j).count_ones() & 1 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-10;
+
+    fn assert_identity(matrix: &Matrix<f64>, scale: f64) {
+        for (i, row) in matrix.iter_rows().enumerate() {
+            for (j, item) in row.iter().enumerate() {
+                let expected = if i == j { scale } else { 0.0 };
+
+                assert!((item - expected).abs() < TOLERANCE, "({i}, {j}) = {item}");
+            }
+        }
+    }
+
+    #[test]
+    fn random_orthogonal_has_orthonormal_columns() {
+        let rng = &mut StdRng::seed_from_u64(1);
+
+        for (rows, columns) in [(5, 5), (40, 7)] {
+            let q = Matrix::random_orthogonal(rows, columns, rng).unwrap();
+
+            assert_eq!(q.shape(), (rows, columns));
+            assert_identity(&(&q.transpose() * &q).unwrap(), 1.0);
+        }
+
+        assert!(Matrix::random_orthogonal(3, 4, rng).is_err());
+    }
+
+    #[test]
+    fn random_permutation_has_a_single_one_per_row_and_column() {
+        let permutation = Matrix::random_permutation(50, &mut StdRng::seed_from_u64(2));
+
+        for row in permutation.iter_rows() {
+            assert_eq!(row.iter().filter(|item| **item == 1.0).count(), 1);
+            assert_eq!(row.iter().filter(|item| **item == 0.0).count(), 49);
+        }
+
+        for column in permutation.iter_columns() {
+            assert_eq!(column.iter().filter(|item| **item == 1.0).count(), 1);
+        }
+    }
+
+    #[test]
+    fn hadamard_is_orthonormal() {
+        for size in [1, 2, 16] {
+            let hadamard = Matrix::hadamard(size).unwrap();
+
+            // the signs times √size form the unnormalized matrix with `H H^T = size I`
+            let signs = hadamard.clone() * (size as f64).sqrt();
+
+            assert_identity(&(&signs * &signs.transpose()).unwrap(), size as f64);
+            assert_identity(&(&hadamard * &hadamard.transpose()).unwrap(), 1.0);
+        }
+
+        assert!(Matrix::hadamard(12).is_err());
+    }
+
+    #[test]
+    fn random_hadamard_is_orthogonal() {
+        let matrix = Matrix::random_hadamard(32, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        assert_identity(&(&matrix.transpose() * &matrix).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn sparse_sign_rejects_sparsity_below_one() {
+        assert!(SparseSign::new(0.5).is_err());
+        assert!(SparseSign::new(f64::INFINITY).is_err());
+        assert!(SparseSign::new(3.0).is_ok());
+    }
+}
@@ -0,0 +1,74 @@
+use std::f64::consts::PI;
+
+/// Distribution of the entries regenerated by a [`SeededProjection`]
+#[derive(Debug, Clone, Copy)]
+pub enum SeededEntries {
+    Gaussian,
+    Sign,
+}
+
+/// A k x n random projection whose entries are never stored, every entry is
+/// recomputed deterministically from the seed and its (row, column) position
+#[derive(Debug, Clone)]
+pub struct SeededProjection {
+    seed: u64,
+    k: usize,
+    entries: SeededEntries,
+}
+
+impl SeededProjection {
+    pub fn new(seed: u64, k: usize, entries: SeededEntries) -> Self {
+        Self { seed, k, entries }
+    }
+
+    /// Entry (row, column) of the projection, normalized so that `E[|Ax|^2] = |x|^2`
+    pub fn entry(&self, row: usize, column: usize) -> f64 {
+        let hash = splitmix64(self.seed ^ splitmix64(row as u64 ^ splitmix64(column as u64)));
+
+        let value = match self.entries {
+            SeededEntries::Gaussian => {
+                // Box-Muller transform over two uniforms derived from the hash
+                let u1 = 1.0 - to_unit(hash);
+                let u2 = to_unit(splitmix64(hash));
+
+                (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+            }
+            SeededEntries::Sign => {
+                if hash & 1 == 0 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        };
+
+        value / (self.k as f64).sqrt()
+    }
+
+    /// Projects a stream of coordinates, one column at a time, into a vector of length k
+    pub fn apply<TIterator: IntoIterator<Item = f64>>(&self, items: TIterator) -> Vec<f64> {
+        let mut result = vec![0.0; self.k];
+
+        for (column, item) in items.into_iter().enumerate() {
+            if item != 0.0 {
+                for (row, value) in result.iter_mut().enumerate() {
+                    *value += self.entry(row, column) * item;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Maps a hash to a uniform value in [0, 1)
+fn to_unit(hash: u64) -> f64 {
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
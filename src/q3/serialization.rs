@@ -0,0 +1,635 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Error, Result};
+
+use crate::error::CliError;
+
+use super::{
+    matrix::Matrix,
+    operator::LinearOperator,
+    sparse_matrix::{SparseLayout, SparseMatrix},
+};
+
+/// Leading bytes of the binary matrix format, followed by the row and column
+/// counts as little endian u64 and the row-major items as little endian f64
+const BINARY_MAGIC: &[u8; 4] = b"MAT1";
+
+#[derive(Debug, Clone, Copy)]
+pub enum MatrixFormat {
+    Csv,
+    Binary,
+    MatrixMarket,
+}
+
+impl MatrixFormat {
+    /// Picks the format by file extension (.csv, .bin, .mtx)
+    pub fn from_path(path: &str) -> Result<Self> {
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("csv") => Ok(MatrixFormat::Csv),
+            Some("bin") => Ok(MatrixFormat::Binary),
+            Some("mtx") => Ok(MatrixFormat::MatrixMarket),
+            _ => Err(format_error(format!(
+                "unknown matrix file extension, expected .csv, .bin or .mtx [path={}]",
+                path
+            ))),
+        }
+    }
+}
+
+impl Matrix<f64> {
+    /// Reads a matrix from a file in the format matching its extension
+    pub fn read_file(path: &str) -> Result<Self> {
+        let reader = BufReader::new(open(path)?);
+
+        match MatrixFormat::from_path(path)? {
+            MatrixFormat::Csv => Matrix::read_csv(reader),
+            MatrixFormat::Binary => Matrix::read_binary(reader),
+            MatrixFormat::MatrixMarket => Matrix::read_matrix_market(reader),
+        }
+    }
+
+    /// Writes the matrix to a file in the format matching its extension
+    pub fn write_file(&self, path: &str) -> Result<()> {
+        let format = MatrixFormat::from_path(path)?;
+        let mut writer = BufWriter::new(create(path)?);
+
+        match format {
+            MatrixFormat::Csv => self.write_csv(&mut writer)?,
+            MatrixFormat::Binary => self.write_binary(&mut writer)?,
+            MatrixFormat::MatrixMarket => self.write_matrix_market(&mut writer)?,
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Reads one row per non empty line with comma separated items
+    pub fn read_csv<R: BufRead>(reader: R) -> Result<Self> {
+        let mut rows = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            rows.push(
+                line.split(',')
+                    .map(|item| parse_item(item.trim()))
+                    .collect::<Result<Vec<f64>>>()?,
+            );
+        }
+
+        Ok(Matrix::from_rows(rows)?)
+    }
+
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> Result<()> {
+        for row in self.iter_rows() {
+            let line = row
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<String>>()
+                .join(",");
+
+            writeln!(writer, "{}", line)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_binary<R: Read>(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        read_header(&mut reader, &mut magic)?;
+
+        if &magic != BINARY_MAGIC {
+            return Err(format_error("not a binary matrix file [invalid magic]"));
+        }
+
+        let rows = read_u64(&mut reader)? as usize;
+        let columns = read_u64(&mut reader)? as usize;
+
+        let mut items = Vec::new();
+        reader.read_to_end(&mut items)?;
+
+        // the header is checked against the actual items before allocating anything
+        let expected = rows
+            .checked_mul(columns)
+            .and_then(|count| count.checked_mul(8));
+
+        if expected != Some(items.len()) {
+            return Err(format_error(format!(
+                "binary matrix size does not match its items [rows={} columns={} item_bytes={}]",
+                rows,
+                columns,
+                items.len()
+            )));
+        }
+
+        let mut matrix = Matrix::zeros(rows, columns);
+        let mut chunks = items.chunks_exact(8);
+
+        for i in 0..rows {
+            for (item, bytes) in matrix.row_mut(i).iter_mut().zip(&mut chunks) {
+                let mut item_bytes = [0u8; 8];
+                item_bytes.copy_from_slice(bytes);
+
+                *item = f64::from_le_bytes(item_bytes);
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&(self.row_count() as u64).to_le_bytes())?;
+        writer.write_all(&(self.column_count() as u64).to_le_bytes())?;
+
+        for row in self.iter_rows() {
+            for item in row {
+                writer.write_all(&item.to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a real, integer or pattern Matrix Market file in coordinate or array layout,
+    /// duplicate coordinates are summed like in [`SparseMatrix::from_triplets`]
+    pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self> {
+        let (rows, columns, triplets) = read_matrix_market_triplets(reader)?;
+
+        // the size line alone must not be able to request an unbounded allocation
+        if rows
+            .checked_mul(columns)
+            .and_then(|count| count.checked_mul(8))
+            .is_none()
+        {
+            return Err(format_error(format!(
+                "matrix market size is too large for a dense matrix [rows={} columns={}]",
+                rows, columns
+            )));
+        }
+
+        let mut matrix = Matrix::zeros(rows, columns);
+
+        for (i, j, value) in triplets {
+            matrix[(i, j)] += value;
+        }
+
+        Ok(matrix)
+    }
+
+    /// Writes the non zero items in the coordinate real general layout
+    pub fn write_matrix_market<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let triplets = self
+            .iter_rows()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, item)| **item != 0.0)
+                    .map(move |(j, item)| (i, j, *item))
+            })
+            .collect::<Vec<(usize, usize, f64)>>();
+
+        write_matrix_market_triplets(writer, self.row_count(), self.column_count(), &triplets)
+    }
+}
+
+impl SparseMatrix {
+    /// Reads a matrix file in the format matching its extension, Matrix Market files are never densified
+    pub fn read_file(path: &str, layout: SparseLayout) -> Result<Self> {
+        match MatrixFormat::from_path(path)? {
+            MatrixFormat::MatrixMarket => {
+                let (rows, columns, triplets) =
+                    read_matrix_market_triplets(BufReader::new(open(path)?))?;
+
+                SparseMatrix::from_triplets(rows, columns, triplets, layout)
+            }
+            _ => SparseMatrix::from_dense(&Matrix::read_file(path)?, layout),
+        }
+    }
+
+    /// Writes the matrix to a file in the format matching its extension, Matrix Market files are never densified
+    pub fn write_file(&self, path: &str) -> Result<()> {
+        match MatrixFormat::from_path(path)? {
+            MatrixFormat::MatrixMarket => {
+                let mut writer = BufWriter::new(create(path)?);
+
+                // row-major like the dense writer, so both write the same file for the same matrix
+                let triplets: Vec<(usize, usize, f64)> = match self.layout() {
+                    SparseLayout::Csr => self.triplets().collect(),
+                    SparseLayout::Csc => self.with_layout(SparseLayout::Csr)?.triplets().collect(),
+                };
+
+                write_matrix_market_triplets(
+                    &mut writer,
+                    self.row_count(),
+                    self.column_count(),
+                    &triplets,
+                )?;
+
+                writer.flush()?;
+
+                Ok(())
+            }
+            _ => self.to_dense().write_file(path),
+        }
+    }
+}
+
+/// Row count, column count and (row, column, value) triplets of a Matrix Market file
+type MatrixMarketContent = (usize, usize, Vec<(usize, usize, f64)>);
+
+/// Parses a Matrix Market file, the mirrored items of symmetric matrices are included
+fn read_matrix_market_triplets<R: BufRead>(reader: R) -> Result<MatrixMarketContent> {
+    let mut lines = reader.lines();
+
+    let header = lines
+        .next()
+        .ok_or(format_error("matrix market file is empty"))??
+        .to_lowercase();
+    let header = header.split_whitespace().collect::<Vec<&str>>();
+
+    if header.len() != 5 || header[0] != "%%matrixmarket" || header[1] != "matrix" {
+        return Err(format_error(format!(
+            "invalid matrix market header [header={}]",
+            header.join(" ")
+        )));
+    }
+
+    let (layout, field, symmetry) = (header[2], header[3], header[4]);
+
+    if !matches!(field, "real" | "integer" | "pattern") {
+        return Err(format_error(format!(
+            "unsupported matrix market field [field={}]",
+            field
+        )));
+    }
+
+    if !matches!(symmetry, "general" | "symmetric" | "skew-symmetric") {
+        return Err(format_error(format!(
+            "unsupported matrix market symmetry [symmetry={}]",
+            symmetry
+        )));
+    }
+
+    let mut entries = Vec::new();
+
+    for line in lines {
+        let line = line?;
+        let line = line.trim();
+
+        if !line.is_empty() && !line.starts_with('%') {
+            entries.push(
+                line.split_whitespace()
+                    .map(str::to_string)
+                    .collect::<Vec<String>>(),
+            );
+        }
+    }
+
+    let mut entries = entries.into_iter();
+    let size = entries
+        .next()
+        .ok_or(format_error("matrix market file has no size line"))?;
+
+    let expected_size_length = if layout == "coordinate" { 3 } else { 2 };
+
+    if size.len() != expected_size_length {
+        return Err(format_error(format!(
+            "invalid matrix market size line [line={}]",
+            size.join(" ")
+        )));
+    }
+
+    let (rows, columns) = (parse_index(&size[0])?, parse_index(&size[1])?);
+
+    if symmetry != "general" && rows != columns {
+        return Err(format_error(format!(
+            "symmetric matrix market file must be square [rows={} columns={}]",
+            rows, columns
+        )));
+    }
+
+    let mut triplets = Vec::new();
+
+    let mut push = |i: usize, j: usize, value: f64| -> Result<()> {
+        if i >= rows || j >= columns {
+            return Err(format_error(format!(
+                "matrix market entry out of range [i={} j={}]",
+                i + 1,
+                j + 1
+            )));
+        }
+
+        triplets.push((i, j, value));
+
+        match symmetry {
+            "symmetric" if i != j => triplets.push((j, i, value)),
+            "skew-symmetric" if i != j => triplets.push((j, i, -value)),
+            _ => {}
+        }
+
+        Ok(())
+    };
+
+    match layout {
+        "coordinate" => {
+            let nonzero_count = parse_index(&size[2])?;
+            let entries = entries.collect::<Vec<Vec<String>>>();
+
+            if nonzero_count != entries.len() {
+                return Err(format_error(format!(
+                    "matrix market entry count does not match its size line [nonzero_count={} entries={}]",
+                    nonzero_count,
+                    entries.len()
+                )));
+            }
+
+            for entry in entries {
+                if entry.len() < 2 {
+                    return Err(format_error(format!(
+                        "invalid matrix market coordinate entry [entry={}]",
+                        entry.join(" ")
+                    )));
+                }
+
+                let (i, j) = (parse_index(&entry[0])?, parse_index(&entry[1])?);
+
+                if i == 0 || j == 0 {
+                    return Err(format_error(format!(
+                        "matrix market coordinates are 1-based [i={} j={}]",
+                        i, j
+                    )));
+                }
+
+                let value = if field == "pattern" {
+                    1.0
+                } else {
+                    parse_item(
+                        entry
+                            .get(2)
+                            .ok_or(format_error("matrix market entry is missing its value"))?,
+                    )?
+                };
+
+                push(i - 1, j - 1, value)?;
+            }
+        }
+        "array" => {
+            // column-major, only the lower triangle is stored for symmetric matrices and
+            // only the strictly lower one for skew-symmetric matrices whose diagonal is zero
+            let first_row = move |j: usize| match symmetry {
+                "general" => 0,
+                "symmetric" => j,
+                _ => j + 1,
+            };
+            let positions = (0..columns).flat_map(|j| (first_row(j)..rows).map(move |i| (i, j)));
+
+            let entries = entries.collect::<Vec<Vec<String>>>();
+            let expected = match symmetry {
+                "general" => rows.checked_mul(columns),
+                "symmetric" => rows
+                    .checked_mul(rows.saturating_add(1))
+                    .map(|count| count / 2),
+                _ => rows
+                    .checked_mul(rows.saturating_sub(1))
+                    .map(|count| count / 2),
+            };
+
+            if expected != Some(entries.len()) {
+                return Err(format_error(format!(
+                    "matrix market array entry count does not match its size [rows={} columns={} entries={}]",
+                    rows,
+                    columns,
+                    entries.len()
+                )));
+            }
+
+            for ((i, j), entry) in positions.zip(entries) {
+                push(i, j, parse_item(&entry[0])?)?;
+            }
+        }
+        _ => {
+            return Err(format_error(format!(
+                "unsupported matrix market layout [layout={}]",
+                layout
+            )))
+        }
+    }
+
+    Ok((rows, columns, triplets))
+}
+
+fn write_matrix_market_triplets<W: Write>(
+    writer: &mut W,
+    rows: usize,
+    columns: usize,
+    triplets: &[(usize, usize, f64)],
+) -> Result<()> {
+    writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+    writeln!(writer, "{} {} {}", rows, columns, triplets.len())?;
+
+    for (i, j, value) in triplets {
+        writeln!(writer, "{} {} {}", i + 1, j + 1, value)?;
+    }
+
+    Ok(())
+}
+
+fn open(path: &str) -> Result<File> {
+    File::open(path).with_context(|| format!("cannot open matrix file [path={}]", path))
+}
+
+fn create(path: &str) -> Result<File> {
+    File::create(path).with_context(|| format!("cannot create matrix file [path={}]", path))
+}
+
+fn parse_item(item: &str) -> Result<f64> {
+    item.parse::<f64>()
+        .map_err(|error| format_error(format!("invalid matrix item [item={}]: {}", item, error)))
+}
+
+fn parse_index(item: &str) -> Result<usize> {
+    item.parse::<usize>()
+        .map_err(|error| format_error(format!("invalid matrix index [item={}]: {}", item, error)))
+}
+
+fn format_error<TMessage: ToString>(message: TMessage) -> Error {
+    CliError::Format(message.to_string()).into()
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut bytes = [0u8; 8];
+    read_header(reader, &mut bytes)?;
+
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads a fixed size header field, a file ending inside the header is malformed rather than unreadable
+fn read_header<R: Read>(reader: &mut R, bytes: &mut [u8]) -> Result<()> {
+    reader
+        .read_exact(bytes)
+        .map_err(|error| match error.kind() {
+            io::ErrorKind::UnexpectedEof => format_error("binary matrix header is truncated"),
+            _ => error.into(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Matrix<f64> {
+        Matrix::from_rows(vec![
+            vec![1.5, 0.0, -2.0],
+            vec![0.0, 0.0, 0.0],
+            vec![3.25, -0.125, 1e-300],
+        ])
+        .unwrap()
+    }
+
+    fn assert_same(left: &Matrix<f64>, right: &Matrix<f64>) {
+        assert_eq!(left.shape(), right.shape());
+        assert!(left.iter_rows().eq(right.iter_rows()));
+    }
+
+    fn assert_format_error(result: Result<impl std::fmt::Debug>) {
+        let error = result.unwrap_err();
+
+        assert!(
+            matches!(error.downcast_ref::<CliError>(), Some(CliError::Format(_))),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let mut bytes = Vec::new();
+        sample().write_csv(&mut bytes).unwrap();
+
+        assert_same(&Matrix::read_csv(bytes.as_slice()).unwrap(), &sample());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let mut bytes = Vec::new();
+        sample().write_binary(&mut bytes).unwrap();
+
+        assert_same(&Matrix::read_binary(bytes.as_slice()).unwrap(), &sample());
+    }
+
+    #[test]
+    fn matrix_market_round_trip() {
+        let mut bytes = Vec::new();
+        sample().write_matrix_market(&mut bytes).unwrap();
+
+        assert_same(
+            &Matrix::read_matrix_market(bytes.as_slice()).unwrap(),
+            &sample(),
+        );
+    }
+
+    #[test]
+    fn file_round_trip_by_extension() {
+        for extension in ["csv", "bin", "mtx"] {
+            let path = std::env::temp_dir().join(format!(
+                "serialization-round-trip-{}.{}",
+                std::process::id(),
+                extension
+            ));
+            let path = path.to_str().unwrap();
+
+            sample().write_file(path).unwrap();
+            let dense = Matrix::read_file(path);
+            let sparse = SparseMatrix::read_file(path, SparseLayout::Csc);
+            std::fs::remove_file(path).unwrap();
+
+            assert_same(&dense.unwrap(), &sample());
+            assert_same(&sparse.unwrap().to_dense(), &sample());
+        }
+    }
+
+    #[test]
+    fn unknown_extension_is_a_format_error() {
+        assert_format_error(MatrixFormat::from_path("matrix.txt"));
+    }
+
+    #[test]
+    fn malformed_csv_is_a_format_error() {
+        assert_format_error(Matrix::read_csv("1,2\n3,x\n".as_bytes()));
+    }
+
+    #[test]
+    fn malformed_binary_is_a_format_error() {
+        let mut bytes = Vec::new();
+        sample().write_binary(&mut bytes).unwrap();
+
+        assert_format_error(Matrix::read_binary(&b"MAT2"[..]));
+        assert_format_error(Matrix::read_binary(&bytes[..2]));
+        assert_format_error(Matrix::read_binary(&bytes[..10]));
+        assert_format_error(Matrix::read_binary(&bytes[..bytes.len() - 1]));
+    }
+
+    #[test]
+    fn malformed_matrix_market_is_a_format_error() {
+        for content in [
+            "",
+            "%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2\n1 1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n0 1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n1.5 1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n1 1\n",
+            "%%MatrixMarket matrix coordinate real general\n-2 2 0\n",
+            "%%MatrixMarket matrix coordinate real symmetric\n2 3 0\n",
+            "%%MatrixMarket matrix array real general\n2 2\n1\n2\n3\n",
+            "%%MatrixMarket matrix coordinate real general\n4294967296 4294967296 0\n",
+        ] {
+            assert_format_error(Matrix::read_matrix_market(content.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn matrix_market_expands_symmetric_layouts() {
+        let symmetric = "%%MatrixMarket matrix array real symmetric\n2 2\n1\n2\n3\n";
+        let skew = "%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n2 1 4\n";
+
+        assert_same(
+            &Matrix::read_matrix_market(symmetric.as_bytes()).unwrap(),
+            &Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 3.0]]).unwrap(),
+        );
+        assert_same(
+            &Matrix::read_matrix_market(skew.as_bytes()).unwrap(),
+            &Matrix::from_rows(vec![vec![0.0, -4.0], vec![4.0, 0.0]]).unwrap(),
+        );
+    }
+
+    #[test]
+    fn matrix_market_duplicates_are_summed_on_both_paths() {
+        let content = "%%MatrixMarket matrix coordinate real general\n2 2 3\n1 1 1\n1 1 2\n2 2 1\n";
+        let expected = Matrix::from_rows(vec![vec![3.0, 0.0], vec![0.0, 1.0]]).unwrap();
+
+        let (rows, columns, triplets) = read_matrix_market_triplets(content.as_bytes()).unwrap();
+        let sparse =
+            SparseMatrix::from_triplets(rows, columns, triplets, SparseLayout::Csr).unwrap();
+
+        assert_same(
+            &Matrix::read_matrix_market(content.as_bytes()).unwrap(),
+            &expected,
+        );
+        assert_same(&sparse.to_dense(), &expected);
+    }
+}
@@ -0,0 +1,466 @@
+use std::cmp::Ordering;
+
+use anyhow::{Error, Result};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::{
+    matrix::Matrix,
+    operator::{check_length, check_sparse_vector, LinearOperator},
+};
+
+/// Compression direction of a [`SparseMatrix`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SparseLayout {
+    /// Compressed sparse rows, cheap row access and `Ax`
+    Csr,
+    /// Compressed sparse columns, cheap column access and `A^T x`
+    Csc,
+}
+
+/// Sparse matrix with `m` rows and `n` columns in CSR or CSC layout, the
+/// items of major line `i` (a row for CSR, a column for CSC) are
+/// `values[offsets[i]..offsets[i + 1]]` at minor positions `indices[..]`
+#[derive(Debug, Clone)]
+pub struct SparseMatrix {
+    m: usize,
+    n: usize,
+    layout: SparseLayout,
+    offsets: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Builds the matrix from (row, column, value) triplets, duplicates are summed and zeros dropped
+    pub fn from_triplets(
+        m: usize,
+        n: usize,
+        mut triplets: Vec<(usize, usize, f64)>,
+        layout: SparseLayout,
+    ) -> Result<Self> {
+        if let Some((i, j, _)) = triplets.iter().find(|(i, j, _)| *i >= m || *j >= n) {
+            return Err(Error::msg(format!(
+                "triplet out of range [i={} j={} m={} n={}]",
+                i, j, m, n
+            )));
+        }
+
+        let major_count = match layout {
+            SparseLayout::Csr => m,
+            SparseLayout::Csc => n,
+        };
+        let key = |triplet: &(usize, usize, f64)| match layout {
+            SparseLayout::Csr => (triplet.0, triplet.1),
+            SparseLayout::Csc => (triplet.1, triplet.0),
+        };
+
+        triplets.sort_by_key(key);
+
+        let mut offsets = vec![0; major_count + 1];
+        let mut indices = Vec::with_capacity(triplets.len());
+        let mut values: Vec<f64> = Vec::with_capacity(triplets.len());
+        let mut last = None;
+
+        for triplet in triplets.iter() {
+            let (major, minor) = key(triplet);
+
+            if last == Some((major, minor)) {
+                if let Some(value) = values.last_mut() {
+                    *value += triplet.2;
+                }
+            } else {
+                offsets[major + 1] += 1;
+                indices.push(minor);
+                values.push(triplet.2);
+                last = Some((major, minor));
+            }
+        }
+
+        for i in 0..major_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let matrix = SparseMatrix {
+            m,
+            n,
+            layout,
+            offsets,
+            indices,
+            values,
+        };
+
+        // summed duplicates may cancel out, rebuilding drops the resulting zeros
+        if matrix.values.contains(&0.0) {
+            let triplets = matrix
+                .triplets()
+                .filter(|(_, _, value)| *value != 0.0)
+                .collect();
+
+            return SparseMatrix::from_triplets(m, n, triplets, layout);
+        }
+
+        Ok(matrix)
+    }
+
+    pub fn from_dense(matrix: &Matrix<f64>, layout: SparseLayout) -> Result<Self> {
+        let triplets = matrix
+            .iter_rows()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, item)| **item != 0.0)
+                    .map(move |(j, item)| (i, j, *item))
+            })
+            .collect();
+
+        SparseMatrix::from_triplets(matrix.row_count(), matrix.column_count(), triplets, layout)
+    }
+
+    pub fn to_dense(&self) -> Matrix<f64> {
+        let mut matrix = Matrix::zeros(self.m, self.n);
+
+        for (i, j, value) in self.triplets() {
+            matrix[(i, j)] = value;
+        }
+
+        matrix
+    }
+
+    /// Same matrix compressed in the given layout
+    pub fn with_layout(&self, layout: SparseLayout) -> Result<Self> {
+        if layout == self.layout {
+            return Ok(self.clone());
+        }
+
+        SparseMatrix::from_triplets(self.m, self.n, self.triplets().collect(), layout)
+    }
+
+    /// `A^T`, a CSR matrix is the CSC representation of its transpose so no data moves
+    pub fn transpose(self) -> Self {
+        SparseMatrix {
+            m: self.n,
+            n: self.m,
+            layout: match self.layout {
+                SparseLayout::Csr => SparseLayout::Csc,
+                SparseLayout::Csc => SparseLayout::Csr,
+            },
+            ..self
+        }
+    }
+
+    pub fn layout(&self) -> SparseLayout {
+        self.layout
+    }
+
+    pub fn nonzero_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Minor indices and values of major line i (row i for CSR, column i for CSC)
+    pub fn major(&self, i: usize) -> (&[usize], &[f64]) {
+        let range = self.offsets[i]..self.offsets[i + 1];
+
+        (&self.indices[range.clone()], &self.values[range])
+    }
+
+    /// Non zero items as (row, column, value)
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        (0..self.major_count()).flat_map(move |major| {
+            let (indices, values) = self.major(major);
+
+            indices
+                .iter()
+                .zip(values.iter())
+                .map(move |(minor, value)| match self.layout {
+                    SparseLayout::Csr => (major, *minor, *value),
+                    SparseLayout::Csc => (*minor, major, *value),
+                })
+        })
+    }
+
+    fn major_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn minor_count(&self) -> usize {
+        match self.layout {
+            SparseLayout::Csr => self.n,
+            SparseLayout::Csc => self.m,
+        }
+    }
+
+    /// Dot product of every major line with x, `Ax` for CSR and `A^T x` for CSC
+    fn gather(&self, vector: &[f64]) -> Vec<f64> {
+        (0..self.major_count())
+            .into_par_iter()
+            .map(|major| {
+                let (indices, values) = self.major(major);
+
+                indices
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(minor, value)| value * vector[*minor])
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Sum of the major lines scaled by x, `A^T x` for CSR and `Ax` for CSC
+    fn scatter<TIterator: Iterator<Item = (usize, f64)>>(&self, vector: TIterator) -> Vec<f64> {
+        let mut result = vec![0.0; self.minor_count()];
+
+        for (major, scale) in vector {
+            let (indices, values) = self.major(major);
+
+            for (minor, value) in indices.iter().zip(values.iter()) {
+                result[*minor] += value * scale;
+            }
+        }
+
+        result
+    }
+}
+
+impl LinearOperator for SparseMatrix {
+    fn row_count(&self) -> usize {
+        self.m
+    }
+
+    fn column_count(&self) -> usize {
+        self.n
+    }
+
+    fn multiply(&self, vector: &[f64]) -> Result<Vec<f64>> {
+        check_length((self.m, self.n), vector.len())?;
+
+        Ok(match self.layout {
+            SparseLayout::Csr => self.gather(vector),
+            SparseLayout::Csc => self.scatter(vector.iter().copied().enumerate()),
+        })
+    }
+
+    fn transpose_multiply(&self, vector: &[f64]) -> Result<Vec<f64>> {
+        check_length((self.n, self.m), vector.len())?;
+
+        Ok(match self.layout {
+            SparseLayout::Csr => self.scatter(vector.iter().copied().enumerate()),
+            SparseLayout::Csc => self.gather(vector),
+        })
+    }
+
+    fn multiply_sparse(&self, indices: &[usize], values: &[f64]) -> Result<Vec<f64>> {
+        check_sparse_vector(self.n, indices, values)?;
+
+        Ok(match self.layout {
+            SparseLayout::Csc => self.scatter(indices.iter().copied().zip(values.iter().copied())),
+            SparseLayout::Csr => (0..self.m)
+                .into_par_iter()
+                .map(|row| {
+                    let (row_indices, row_values) = self.major(row);
+
+                    sparse_dot(row_indices, row_values, indices, values)
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Dot product of two sparse vectors with ascending indices
+pub fn sparse_dot(
+    left_indices: &[usize],
+    left_values: &[f64],
+    right_indices: &[usize],
+    right_values: &[f64],
+) -> f64 {
+    let (mut i, mut j, mut sum) = (0, 0, 0.0);
+
+    while i < left_indices.len() && j < right_indices.len() {
+        match left_indices[i].cmp(&right_indices[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                sum += left_values[i] * right_values[j];
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    sum
+}
+
+/// Squared l2 distance of two sparse vectors with ascending indices
+pub fn sparse_squared_distance(
+    left_indices: &[usize],
+    left_values: &[f64],
+    right_indices: &[usize],
+    right_values: &[f64],
+) -> f64 {
+    let (mut i, mut j, mut sum) = (0, 0, 0.0);
+
+    while i < left_indices.len() || j < right_indices.len() {
+        let order = match (left_indices.get(i), right_indices.get(j)) {
+            (Some(left), Some(right)) => left.cmp(right),
+            (Some(_), None) => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+
+        let difference = match order {
+            Ordering::Less => {
+                i += 1;
+                left_values[i - 1]
+            }
+            Ordering::Greater => {
+                j += 1;
+                right_values[j - 1]
+            }
+            Ordering::Equal => {
+                i += 1;
+                j += 1;
+                left_values[i - 1] - right_values[j - 1]
+            }
+        };
+
+        sum += difference.powi(2);
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::q3::matrix::MatrixError;
+
+    const LAYOUTS: [SparseLayout; 2] = [SparseLayout::Csr, SparseLayout::Csc];
+
+    fn dense() -> Matrix<f64> {
+        Matrix::from_rows(vec![
+            vec![0.0, 2.0, 0.0, -1.0],
+            vec![0.0, 0.0, 0.0, 0.0],
+            vec![3.0, 0.0, 0.5, 0.0],
+        ])
+        .unwrap()
+    }
+
+    fn assert_same(left: &Matrix<f64>, right: &Matrix<f64>) {
+        assert_eq!(left.shape(), right.shape());
+        assert!(left.iter_rows().eq(right.iter_rows()));
+    }
+
+    #[test]
+    fn dense_round_trip_in_both_layouts() {
+        for layout in LAYOUTS {
+            let sparse = SparseMatrix::from_dense(&dense(), layout).unwrap();
+
+            assert_eq!(sparse.layout(), layout);
+            assert_eq!(sparse.nonzero_count(), 4);
+            assert_same(&sparse.to_dense(), &dense());
+        }
+    }
+
+    #[test]
+    fn products_match_the_dense_matrix_in_both_layouts() {
+        let x = [1.0, -2.0, 0.5, 4.0];
+        let y = [2.0, 7.0, -1.0];
+        let (indices, values) = ([1, 3], [-2.0, 4.0]);
+        let sparse_x = [0.0, -2.0, 0.0, 4.0];
+
+        for layout in LAYOUTS {
+            let sparse = SparseMatrix::from_dense(&dense(), layout).unwrap();
+
+            assert_eq!(sparse.multiply(&x).unwrap(), dense().multiply(&x).unwrap());
+            assert_eq!(
+                sparse.transpose_multiply(&y).unwrap(),
+                dense().transpose_multiply(&y).unwrap()
+            );
+            assert_eq!(
+                sparse.multiply_sparse(&indices, &values).unwrap(),
+                dense().multiply(&sparse_x).unwrap()
+            );
+            assert_eq!(
+                dense().multiply_sparse(&indices, &values).unwrap(),
+                dense().multiply(&sparse_x).unwrap()
+            );
+            assert_same(
+                &sparse.multiply_matrix(&dense().transpose()).unwrap(),
+                &(&dense() * &dense().transpose()).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn transpose_multiply_of_the_dense_matrix_is_the_product_with_its_transpose() {
+        let y = [2.0, 7.0, -1.0];
+
+        assert_eq!(
+            dense().transpose_multiply(&y).unwrap(),
+            (&dense().transpose() * &y[..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn transpose_and_layout_changes_keep_the_items() {
+        for (layout, other_layout) in LAYOUTS.into_iter().zip(LAYOUTS.into_iter().rev()) {
+            let sparse = SparseMatrix::from_dense(&dense(), layout).unwrap();
+            let converted = sparse.with_layout(other_layout).unwrap();
+            let transpose = sparse.transpose();
+
+            assert_eq!(converted.layout(), other_layout);
+            assert_same(&converted.to_dense(), &dense());
+            assert_eq!(transpose.layout(), other_layout);
+            assert_same(&transpose.to_dense(), &dense().transpose());
+        }
+    }
+
+    #[test]
+    fn products_reject_mismatched_lengths_and_unsorted_indices() {
+        for layout in LAYOUTS {
+            let sparse = SparseMatrix::from_dense(&dense(), layout).unwrap();
+
+            assert!(sparse.multiply(&[1.0; 3]).is_err());
+            assert!(sparse.transpose_multiply(&[1.0; 4]).is_err());
+            assert!(sparse.multiply_sparse(&[4], &[1.0]).is_err());
+            assert!(matches!(
+                sparse
+                    .multiply_sparse(&[2, 1], &[1.0, 1.0])
+                    .unwrap_err()
+                    .downcast_ref::<MatrixError>(),
+                Some(MatrixError::UnsortedIndices { position: 1 })
+            ));
+        }
+    }
+
+    #[test]
+    fn from_triplets_sums_duplicates_and_drops_cancelled_items() {
+        for layout in LAYOUTS {
+            let sparse = SparseMatrix::from_triplets(
+                2,
+                2,
+                vec![(0, 0, 1.0), (1, 1, 2.0), (0, 0, 2.0), (1, 1, -2.0)],
+                layout,
+            )
+            .unwrap();
+
+            assert_eq!(sparse.nonzero_count(), 1);
+            assert_eq!(sparse.triplets().collect::<Vec<_>>(), vec![(0, 0, 3.0)]);
+            assert!(SparseMatrix::from_triplets(2, 2, vec![(2, 0, 1.0)], layout).is_err());
+        }
+    }
+
+    #[test]
+    fn sparse_dot_and_distance_match_the_dense_ones() {
+        let (left_indices, left_values) = ([0, 2, 5], [1.0, -2.0, 3.0]);
+        let (right_indices, right_values) = ([2, 3, 5], [4.0, 1.0, -1.0]);
+
+        assert_eq!(
+            sparse_dot(&left_indices, &left_values, &right_indices, &right_values),
+            -11.0
+        );
+        assert_eq!(
+            sparse_squared_distance(&left_indices, &left_values, &right_indices, &right_values),
+            1.0 + 36.0 + 1.0 + 16.0
+        );
+    }
+}
@@ -0,0 +1,522 @@
+use std::{f64::consts::LN_2, fmt::Write, fs, path::Path, time::SystemTime};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use log::info;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    common::{
+        run_report::RunReport,
+        stopping::wilson_interval,
+        store::{utc_timestamp, ResultStore, RunRecord, VERSION},
+        table::{fields, format_cell},
+    },
+    plot::{Question, SecretaryCommand, SuccessRateCommand},
+};
+
+const TITLE: &str = "Random algorithms homework report";
+
+const SECTIONS: [Section; 6] = [
+    Section {
+        name: "q1",
+        title: "Question 1 - secretary problem",
+        algorithm: "Q1 Algorithm",
+        command: "q1",
+        description: "Candidates arrive in a random order, the first half of them is only observed and the first later candidate at least as good as all of them is hired, the last candidate is hired when none is. Skipping a fraction c of the candidates hires the best one with probability about c ln(1/c), ln(2)/2 for the first half and at most 1/e for c = 1/e.",
+        rate_field: Some("hit_percent"),
+        comparisons: secretary_comparisons,
+        figure: Figure::Secretary,
+    },
+    Section {
+        name: "q2-naive",
+        title: "Question 2 - naive L2 estimator",
+        algorithm: "Q2 Naive Algorithm",
+        command: "q2 naive",
+        description: "Every estimate is the squared sum of the array items multiplied by random signs, an unbiased estimate of the squared L2 norm whose variance is at most twice the squared norm squared.",
+        rate_field: None,
+        comparisons: naive_comparisons,
+        figure: Figure::None,
+    },
+    Section {
+        name: "q2-amplificated",
+        title: "Question 2 - amplified L2 estimator",
+        algorithm: "Q2 Amplificated Algorithm",
+        command: "q2 amplificated",
+        description: "The median of 18 ln(2/delta) means of 9/epsilon naive estimates, within (1 +- epsilon) of the squared L2 norm with probability at least 1-delta.",
+        rate_field: Some("succession_ratio"),
+        comparisons: bound_comparisons,
+        figure: Figure::SuccessRate(Question::Q2),
+    },
+    Section {
+        name: "q3",
+        title: "Question 3 - Johnson-Lindenstrauss projection",
+        algorithm: "Q3 Algorithm",
+        command: "q3",
+        description: "The array is multiplied by a random projection to O(ln(1/delta)/epsilon^2) dimensions, the squared norm of the projection is within (1 +- epsilon) of the squared norm of the array with probability at least 1-delta.",
+        rate_field: Some("hit_percent"),
+        comparisons: bound_comparisons,
+        figure: Figure::SuccessRate(Question::Q3),
+    },
+    Section {
+        name: "rnla-least-squares",
+        title: "Sketched least squares",
+        algorithm: "Sketched Least Squares Algorithm",
+        command: "rnla least-squares",
+        description: "An overdetermined system is solved after sketching its rows with a random projection. With a Gaussian sketch of s rows for d unknowns the expected squared residual is 1 + d/(s-d-1) times the optimal one.",
+        rate_field: None,
+        comparisons: least_squares_comparisons,
+        figure: Figure::None,
+    },
+    Section {
+        name: "rnla-svd",
+        title: "Randomized SVD",
+        algorithm: "Randomized SVD Algorithm",
+        command: "rnla svd",
+        description: "A rank k approximation is built from the range of the matrix multiplied by k+p random vectors. The expected Frobenius error is at most sqrt(1 + k/(p-1)) times the error of the optimal rank k approximation (Halko, Martinsson and Tropp, theorem 10.5).",
+        rate_field: None,
+        comparisons: svd_comparisons,
+        figure: Figure::None,
+    },
+];
+
+#[derive(Debug, Args, Serialize)]
+pub struct ReportCommand {
+    ///Path the report will be written to, HTML when it ends with .html and Markdown otherwise
+    #[clap(short, long, default_value = "report.md")]
+    path: String,
+    ///Amount of times the secretary strategy runs for every cutoff of its chart
+    #[clap(short, long, default_value = "1000")]
+    repeat_count: usize,
+    ///Leave the charts out of the report
+    #[clap(long)]
+    no_plots: bool,
+}
+
+impl ReportCommand {
+    pub fn invoke(&self, store: &ResultStore) -> Result<()> {
+        let records = store.records()?;
+
+        let mut blocks = vec![
+            Block::Heading(1, TITLE.into()),
+            Block::Paragraph(format!(
+                "Generated at {} by version {} from the latest complete run of every question recorded in the results store.",
+                utc_timestamp(SystemTime::now()),
+                VERSION
+            )),
+        ];
+
+        for section in &SECTIONS {
+            blocks.extend(self.section(section, &records, store)?);
+        }
+
+        let path = Path::new(&self.path);
+        let html = path
+            .extension()
+            .is_some_and(|extension| extension == "html");
+
+        let document = if html {
+            render_html(&blocks)?
+        } else {
+            let stem = path
+                .file_stem()
+                .map_or("report".into(), |stem| stem.to_string_lossy());
+            let figure_file = |name: &str| format!("{}-{}.svg", stem, name);
+
+            // Markdown cannot hold the charts, they are written next to the report
+            for block in &blocks {
+                if let Block::Figure(name, svg) = block {
+                    let figure_path = path.with_file_name(figure_file(name));
+
+                    fs::write(&figure_path, svg).with_context(|| {
+                        format!("cannot write plot [path={}]", figure_path.display())
+                    })?;
+                }
+            }
+
+            render_markdown(&blocks, figure_file)?
+        };
+
+        fs::write(path, document)
+            .with_context(|| format!("cannot write report [path={}]", self.path))?;
+
+        info!("report written [path={}]", self.path);
+
+        Ok(())
+    }
+
+    fn section(
+        &self,
+        section: &Section,
+        records: &[(usize, RunRecord)],
+        store: &ResultStore,
+    ) -> Result<Vec<Block>> {
+        let mut blocks = vec![
+            Block::Heading(2, section.title.into()),
+            Block::Paragraph(section.description.into()),
+        ];
+
+        let latest = records.iter().rev().find(|(_, record)| {
+            record.report.algorithm == section.algorithm && !record.report.partial
+        });
+
+        let (id, record) = match latest {
+            Some(latest) => latest,
+            None => {
+                blocks.push(Block::Paragraph(format!(
+                    "No complete run is recorded yet, the {} command records one.",
+                    section.command
+                )));
+
+                return Ok(blocks);
+            }
+        };
+
+        let report = &record.report;
+
+        blocks.push(Block::Heading(3, "Run".into()));
+        blocks.push(Block::Table(
+            header(&["field", "value"]),
+            vec![
+                vec!["id".into(), id.to_string()],
+                vec!["timestamp".into(), record.timestamp.clone()],
+                vec!["version".into(), record.version.clone()],
+                vec!["command".into(), record.command.clone()],
+                vec![
+                    "seed".into(),
+                    report.seed.map_or("-".into(), |seed| seed.to_string()),
+                ],
+                vec!["repetitions".into(), report.repetitions.to_string()],
+                vec!["wall_time".into(), format!("{:.3}s", report.wall_time)],
+            ],
+        ));
+
+        blocks.push(Block::Heading(3, "Parameters".into()));
+        blocks.push(Block::Table(
+            header(&["parameter", "value"]),
+            fields("", &report.parameters)
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(field, value)| {
+                    // parameters are nested under their command names
+                    let name = field.rsplit('.').next().unwrap_or_default().to_string();
+
+                    vec![name, format_cell(Some(&value))]
+                })
+                .collect(),
+        ));
+
+        blocks.push(Block::Heading(3, "Results".into()));
+        blocks.push(Block::Table(
+            header(&["field", "value", "95% confidence interval"]),
+            fields("", &report.result)
+                .into_iter()
+                .filter(|(_, value)| !value.is_array())
+                .map(|(field, value)| {
+                    let interval = match section.rate_field {
+                        Some(rate_field) if rate_field == field => success_rate(report, &field)
+                            .map_or("-".into(), |(_, (low, high))| {
+                                format!("{:.2} - {:.2}", low * 100.0, high * 100.0)
+                            }),
+                        _ => "-".into(),
+                    };
+
+                    vec![field, format_cell(Some(&value)), interval]
+                })
+                .collect(),
+        ));
+
+        let comparisons = (section.comparisons)(report);
+
+        if !comparisons.is_empty() {
+            blocks.push(Block::Heading(3, "Theoretical comparison".into()));
+            blocks.push(Block::Table(
+                header(&["quantity", "measured", "theoretical", "consistent"]),
+                comparisons
+                    .into_iter()
+                    .map(|comparison| {
+                        vec![
+                            comparison.quantity,
+                            format!("{:.4}", comparison.measured),
+                            comparison.theoretical,
+                            if comparison.consistent { "yes" } else { "no" }.into(),
+                        ]
+                    })
+                    .collect(),
+            ));
+        }
+
+        if !self.no_plots {
+            let chart = match section.figure {
+                Figure::None => None,
+                Figure::Secretary => {
+                    Some(SecretaryCommand::new(100, self.repeat_count, 20).chart()?)
+                }
+                Figure::SuccessRate(question) => report
+                    .parameter("delta")
+                    .map(|delta| SuccessRateCommand::recorded(question, delta).chart(store))
+                    .transpose()?,
+            };
+
+            if let Some(chart) = chart {
+                blocks.push(Block::Figure(section.name.into(), chart.render()?));
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
+/// Question of the homework along with how its latest recorded run is compared to the theory
+struct Section {
+    /// Registered name of the question, also names its chart file
+    name: &'static str,
+    title: &'static str,
+    /// Name the runs of the question are recorded under
+    algorithm: &'static str,
+    /// Command line recording a run of the question
+    command: &'static str,
+    description: &'static str,
+    /// Result field holding a success rate in percent
+    rate_field: Option<&'static str>,
+    comparisons: fn(&RunReport) -> Vec<Comparison>,
+    figure: Figure,
+}
+
+enum Figure {
+    None,
+    /// Hire rate of the secretary strategy for every cutoff
+    Secretary,
+    /// Success rate of the recorded runs for every epsilon
+    SuccessRate(Question),
+}
+
+/// Measured quantity next to the value the theory predicts for it
+struct Comparison {
+    quantity: String,
+    measured: f64,
+    theoretical: String,
+    consistent: bool,
+}
+
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    Table(Vec<String>, Vec<Vec<String>>),
+    /// SVG chart along with the name of its file
+    Figure(String, String),
+}
+
+fn secretary_comparisons(report: &RunReport) -> Vec<Comparison> {
+    let theoretical = LN_2 / 2.0;
+
+    success_rate(report, "hit_percent")
+        .map(|(rate, (low, high))| Comparison {
+            quantity: "best candidate hire rate".into(),
+            measured: rate,
+            theoretical: format!("{:.4}", theoretical),
+            consistent: low <= theoretical && theoretical <= high,
+        })
+        .into_iter()
+        .collect()
+}
+
+fn naive_comparisons(report: &RunReport) -> Vec<Comparison> {
+    let number = |field: &str| report.result.get(field).and_then(Value::as_f64);
+
+    match (number("average"), number("l2_norm")) {
+        (Some(average), Some(l2_norm)) if l2_norm > 0.0 => {
+            // the mean of n estimates deviates by at most z sqrt(2/n) with 95% confidence
+            let margin = 1.96 * (2.0 / report.repetitions as f64).sqrt();
+            let ratio = average / l2_norm;
+
+            vec![Comparison {
+                quantity: "average estimate / squared norm".into(),
+                measured: ratio,
+                theoretical: format!("1 +- {:.4}", margin),
+                consistent: (ratio - 1.0).abs() <= margin,
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Success rate of the estimates within `(1 +- epsilon)` of the norm against `1-delta`
+fn bound_comparisons(report: &RunReport) -> Vec<Comparison> {
+    let rate = ["succession_ratio", "hit_percent"]
+        .iter()
+        .find_map(|field| success_rate(report, field));
+
+    match (rate, report.parameter("delta")) {
+        (Some((rate, (_, high))), Some(delta)) => vec![Comparison {
+            quantity: "success rate".into(),
+            measured: rate,
+            theoretical: format!(">= {:.4}", 1.0 - delta),
+            consistent: high >= 1.0 - delta,
+        }],
+        _ => Vec::new(),
+    }
+}
+
+fn least_squares_comparisons(report: &RunReport) -> Vec<Comparison> {
+    let measured = report
+        .result
+        .get("average_residual_ratio")
+        .and_then(Value::as_f64);
+    // the columns parameter is ignored when the system matrix is read from a file
+    let columns = report.result.get("columns").and_then(Value::as_f64);
+
+    match (measured, columns, report.parameter("sketch_size")) {
+        (Some(measured), Some(columns), Some(sketch_size)) if sketch_size > columns + 1.0 => {
+            let bound = (1.0 + columns / (sketch_size - columns - 1.0)).sqrt();
+
+            vec![Comparison {
+                quantity: "average residual ratio".into(),
+                measured,
+                theoretical: format!("<= {:.4}", bound),
+                consistent: measured <= bound,
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn svd_comparisons(report: &RunReport) -> Vec<Comparison> {
+    let measured = report
+        .result
+        .get("average_frobenius_error_ratio")
+        .and_then(Value::as_f64);
+
+    match (
+        measured,
+        report.parameter("k"),
+        report.parameter("oversampling"),
+    ) {
+        (Some(measured), Some(k), Some(oversampling)) if oversampling >= 2.0 => {
+            let bound = (1.0 + k / (oversampling - 1.0)).sqrt();
+
+            vec![Comparison {
+                quantity: "average Frobenius error ratio".into(),
+                measured,
+                theoretical: format!("<= {:.4}", bound),
+                consistent: measured <= bound,
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Success rate stored in percent under `field` along with its 95% confidence interval
+fn success_rate(report: &RunReport, field: &str) -> Option<(f64, (f64, f64))> {
+    let rate = report.result.get(field)?.as_f64()? / 100.0;
+
+    // failed repetitions are left out of the rate
+    let failures = report
+        .result
+        .pointer("/failures/count")
+        .and_then(Value::as_u64)
+        .unwrap_or_default() as usize;
+    let completed = report.repetitions.saturating_sub(failures);
+
+    if completed == 0 {
+        return None;
+    }
+
+    let successes = (rate * completed as f64).round() as usize;
+
+    Some((rate, wilson_interval(successes, completed)))
+}
+
+fn header(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn render_markdown(blocks: &[Block], figure_file: impl Fn(&str) -> String) -> Result<String> {
+    let mut markdown = String::new();
+
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => writeln!(markdown, "{} {}\n", "#".repeat(*level), text)?,
+            Block::Paragraph(text) => writeln!(markdown, "{}\n", text)?,
+            Block::Table(header, lines) => {
+                let row = |cells: &[String]| {
+                    format!(
+                        "| {} |",
+                        cells
+                            .iter()
+                            .map(|cell| cell.replace('|', "\\|"))
+                            .collect::<Vec<String>>()
+                            .join(" | ")
+                    )
+                };
+
+                writeln!(markdown, "{}", row(header))?;
+                writeln!(markdown, "|{}", " --- |".repeat(header.len()))?;
+
+                for line in lines {
+                    writeln!(markdown, "{}", row(line))?;
+                }
+
+                writeln!(markdown)?;
+            }
+            Block::Figure(name, _) => writeln!(markdown, "![{}]({})\n", name, figure_file(name))?,
+        }
+    }
+
+    Ok(markdown)
+}
+
+fn render_html(blocks: &[Block]) -> Result<String> {
+    let mut html = String::new();
+
+    writeln!(html, "<!DOCTYPE html>")?;
+    writeln!(html, "<html>")?;
+    writeln!(html, "<head>")?;
+    writeln!(html, r#"<meta charset="utf-8">"#)?;
+    writeln!(html, "<title>{}</title>", TITLE)?;
+    writeln!(
+        html,
+        "<style>body {{ font-family: sans-serif; max-width: 60em; margin: auto; }} table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}</style>"
+    )?;
+    writeln!(html, "</head>")?;
+    writeln!(html, "<body>")?;
+
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                writeln!(html, "<h{level}>{}</h{level}>", escape(text), level = level)?
+            }
+            Block::Paragraph(text) => writeln!(html, "<p>{}</p>", escape(text))?,
+            Block::Table(header, lines) => {
+                let row = |cells: &[String], tag: &str| {
+                    cells
+                        .iter()
+                        .map(|cell| format!("<{tag}>{}</{tag}>", escape(cell), tag = tag))
+                        .collect::<String>()
+                };
+
+                writeln!(html, "<table>")?;
+                writeln!(html, "<tr>{}</tr>", row(header, "th"))?;
+
+                for line in lines {
+                    writeln!(html, "<tr>{}</tr>", row(line, "td"))?;
+                }
+
+                writeln!(html, "</table>")?;
+            }
+            Block::Figure(_, svg) => writeln!(html, "<figure>\n{}</figure>", svg)?,
+        }
+    }
+
+    writeln!(html, "</body>")?;
+    writeln!(html, "</html>")?;
+
+    Ok(html)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,279 @@
+use anyhow::Result;
+use clap::Args;
+use log::{debug, info};
+use nameof::name_of;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{
+        algorithm::Algorithm, random, reduce::IntoReduce, repeat::IntoRepeat,
+        run_report::RunReport, validation::validate_count, with_name::IntoWithName,
+    },
+    error::CliError,
+    extensions::vec_extensions::EuclideanNormExtension,
+    q3::{matrix::Matrix, operator::LinearOperator, projection::ProjectionKind},
+};
+
+use super::EXACT_TOLERANCE;
+
+#[derive(Debug, Args, Serialize, Deserialize)]
+pub struct LeastSquaresCommand {
+    ///Path of a matrix file (.csv, .bin or .mtx) to use instead of a generated system matrix
+    #[clap(short, long)]
+    input: Option<String>,
+    ///Amount of rows (equations) of the generated system
+    #[clap(short = 'm', long, default_value = "2000")]
+    rows: usize,
+    ///Amount of columns (unknowns) of the generated system
+    #[clap(short = 'n', long, default_value = "20")]
+    columns: usize,
+    ///Amount of rows of the sketched system
+    #[clap(short, long, default_value = "200")]
+    sketch_size: usize,
+    ///Standard deviation of the noise added to the right hand side
+    #[clap(long, default_value = "0.1")]
+    noise: f64,
+    ///Generate a coherent system matrix whose rows are zero except for a few random ones, the hard case for sparse sketches
+    #[clap(long)]
+    coherent: bool,
+    ///Kind of random projection used as the sketch
+    #[clap(short, long, arg_enum, default_value = "gaussian")]
+    projection: ProjectionKind,
+    ///Amount of times the algorithm will run
+    #[clap(short, long, default_value = "100")]
+    repeat_count: usize,
+}
+
+impl LeastSquaresCommand {
+    pub fn invoke(&self) -> Result<RunReport> {
+        self.algorithm()?.run()
+    }
+
+    pub fn algorithm(
+        &self,
+    ) -> Result<impl Algorithm<Output = impl Serialize> + Sync + Send + 'static> {
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+
+        let rng = &mut random::rng();
+        let a = match &self.input {
+            Some(path) => Matrix::read_file(path)?,
+            None if self.coherent => {
+                if self.rows < self.columns {
+                    return Err(CliError::invalid_parameter(
+                        name_of!(rows in Self),
+                        self.rows,
+                        &format!(
+                            "a coherent system needs at least as many rows as columns ({})",
+                            self.columns
+                        ),
+                    )
+                    .into());
+                }
+
+                // an orthogonal block padded with zero rows, then shuffled into random rows
+                let block = Matrix::random_orthogonal(self.columns, self.columns, rng)?;
+                let rows = (0..self.rows)
+                    .map(|i| {
+                        if i < self.columns {
+                            block.row(i).to_vec()
+                        } else {
+                            vec![0.0; self.columns]
+                        }
+                    })
+                    .collect();
+
+                (&Matrix::random_permutation(self.rows, rng) * &Matrix::from_rows(rows)?)?
+            }
+            None => Matrix::random_gaussian(self.rows, self.columns, 1.0, rng)?,
+        };
+
+        if self.sketch_size < a.column_count() {
+            return Err(CliError::invalid_parameter(
+                name_of!(sketch_size in Self),
+                self.sketch_size,
+                &format!(
+                    "must be at least the amount of columns ({})",
+                    a.column_count()
+                ),
+            )
+            .into());
+        }
+
+        let x = Matrix::random_gaussian(a.column_count(), 1, 1.0, rng)?;
+        let noise = Matrix::random_gaussian(a.row_count(), 1, self.noise, rng)?;
+        let b: Vec<f64> = (&(&a * &x)? + &noise)?.try_into()?;
+
+        let exact_solution = a.solve_least_squares(&b)?;
+        let exact_residual = residual(&a, &exact_solution, &b)?;
+
+        // every ratio is measured against the exact solution, so it must satisfy the
+        // normal equations `A^T (b - Ax) = 0` up to rounding
+        let fitted = (&a * exact_solution.as_slice())?;
+        let residual_vector = b
+            .iter()
+            .zip(fitted.iter())
+            .map(|(b, fitted)| b - fitted)
+            .collect::<Vec<f64>>();
+        let normal_equations_error = a.transpose_multiply(&residual_vector)?.euclidean_norm();
+        let a_norm = a.frobenius_norm();
+
+        if normal_equations_error
+            > EXACT_TOLERANCE
+                * a_norm
+                * (a_norm * exact_solution.euclidean_norm() + b.euclidean_norm())
+        {
+            return Err(CliError::Numeric(format!(
+                "the exact solution does not satisfy the normal equations, the matrix is too ill-conditioned [{}={}]",
+                name_of!(normal_equations_error),
+                normal_equations_error
+            ))
+            .into());
+        }
+
+        info!(
+            "exact solution computed [{}={}]",
+            name_of!(exact_residual),
+            exact_residual
+        );
+
+        if exact_residual <= EXACT_TOLERANCE * b.euclidean_norm() {
+            return Err(CliError::Numeric(format!(
+                "the system is consistent, so the optimal residual is 0 and residual ratios are undefined [{}={}]",
+                name_of!(exact_residual),
+                exact_residual
+            ))
+            .into());
+        }
+
+        if exact_solution.euclidean_norm() == 0.0 {
+            return Err(CliError::Numeric(
+                "the exact solution is 0, so relative solution errors are undefined".into(),
+            )
+            .into());
+        }
+
+        Ok(SketchedLeastSquaresAlgorithm {
+            input: SketchedLeastSquaresAlgorithmInput {
+                rows: a.row_count(),
+                columns: a.column_count(),
+                sketch_size: self.sketch_size,
+                projection: self.projection,
+            },
+            a,
+            b,
+            exact_solution,
+            exact_residual,
+        }
+        .repeat(self.repeat_count)
+        .reduce(|series, (input, _)| {
+            let count = series.len() as f64;
+
+            Ok(LeastSquaresResult {
+                projection: input.projection,
+                rows: input.rows,
+                columns: input.columns,
+                average_residual_ratio: series
+                    .iter()
+                    .map(|estimate| estimate.residual_ratio)
+                    .sum::<f64>()
+                    / count,
+                max_residual_ratio: series
+                    .iter()
+                    .map(|estimate| estimate.residual_ratio)
+                    .fold(0.0, f64::max),
+                average_relative_solution_error: series
+                    .iter()
+                    .map(|estimate| estimate.relative_solution_error)
+                    .sum::<f64>()
+                    / count,
+            })
+        })
+        .with_name("Sketched Least Squares Algorithm".into()))
+    }
+}
+
+fn residual(a: &Matrix<f64>, x: &[f64], b: &[f64]) -> Result<f64> {
+    Ok((a * x)?.euclidean_distance(b))
+}
+
+#[derive(Debug, Clone)]
+struct SketchedLeastSquaresAlgorithmInput {
+    rows: usize,
+    columns: usize,
+    sketch_size: usize,
+    projection: ProjectionKind,
+}
+
+#[derive(Debug)]
+struct SketchedLeastSquaresEstimate {
+    /// `|Ax' - b| / |Ax* - b|` for the sketched solution x' and the exact solution x*
+    residual_ratio: f64,
+    /// `|x' - x*| / |x*|`
+    relative_solution_error: f64,
+}
+
+struct SketchedLeastSquaresAlgorithm {
+    input: SketchedLeastSquaresAlgorithmInput,
+    a: Matrix<f64>,
+    b: Vec<f64>,
+    exact_solution: Vec<f64>,
+    exact_residual: f64,
+}
+
+impl Algorithm for SketchedLeastSquaresAlgorithm {
+    type Input = SketchedLeastSquaresAlgorithmInput;
+    type Output = SketchedLeastSquaresEstimate;
+
+    fn name(&self) -> String {
+        "Sketched Least Squares Algorithm".into()
+    }
+
+    fn input(&self) -> Self::Input {
+        self.input.clone()
+    }
+
+    fn run_internal<F: Fn() + Send + Sync>(
+        &self,
+        update_progress: F,
+    ) -> Result<SketchedLeastSquaresEstimate> {
+        debug!("run_internal started");
+
+        let sketch = self.input.projection.generate(
+            self.input.sketch_size,
+            self.input.rows,
+            &mut random::rng(),
+        )?;
+
+        let sketched_a = (&sketch * &self.a)?;
+        let sketched_b = (&sketch * &self.b)?;
+        let solution = sketched_a.solve_least_squares(&sketched_b)?;
+
+        let residual_ratio = residual(&self.a, &solution, &self.b)? / self.exact_residual;
+        let relative_solution_error = solution.euclidean_distance(&self.exact_solution)
+            / self.exact_solution.euclidean_norm();
+
+        debug!(
+            "run_internal finished [{}={}]",
+            name_of!(residual_ratio),
+            residual_ratio
+        );
+
+        update_progress();
+
+        Ok(SketchedLeastSquaresEstimate {
+            residual_ratio,
+            relative_solution_error,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LeastSquaresResult {
+    projection: ProjectionKind,
+    /// Shape of the system matrix, read from the input file when one is given
+    rows: usize,
+    columns: usize,
+    average_residual_ratio: f64,
+    max_residual_ratio: f64,
+    average_relative_solution_error: f64,
+}
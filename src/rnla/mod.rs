@@ -0,0 +1,50 @@
+mod least_squares;
+mod randomized_svd;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::common::{registry::Registry, run_report::RunReport};
+
+use self::{least_squares::LeastSquaresCommand, randomized_svd::RandomizedSvdCommand};
+
+#[derive(Debug, Args, Serialize)]
+pub struct RnlaCommand {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(
+        "rnla-least-squares",
+        "Solves a generated least squares problem by sketch-and-solve",
+        LeastSquaresCommand::algorithm,
+    );
+    registry.register(
+        "rnla-svd",
+        "Computes a low rank approximation of a generated matrix by randomized SVD",
+        RandomizedSvdCommand::algorithm,
+    );
+}
+
+impl RnlaCommand {
+    pub fn invoke(&self) -> Result<RunReport> {
+        match &self.command {
+            Commands::LeastSquares(command) => command.invoke(),
+            Commands::Svd(command) => command.invoke(),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand, Serialize)]
+enum Commands {
+    ///Solves a generated least squares problem by sketch-and-solve
+    LeastSquares(LeastSquaresCommand),
+    ///Computes a low rank approximation of a generated matrix by randomized SVD
+    Svd(RandomizedSvdCommand),
+}
+
+/// Optimal errors below this fraction of the scale of the problem are round-off of an exact
+/// solution, error ratios against them are meaningless
+const EXACT_TOLERANCE: f64 = 1e-10;
@@ -0,0 +1,290 @@
+use anyhow::Result;
+use clap::Args;
+use log::{debug, info};
+use nameof::name_of;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{
+        algorithm::Algorithm, random, reduce::IntoReduce, repeat::IntoRepeat,
+        run_report::RunReport, validation::validate_count, with_name::IntoWithName,
+    },
+    error::CliError,
+    q3::{
+        matrix::Matrix,
+        operator::LinearOperator,
+        projection::ProjectionKind,
+        serialization::MatrixFormat,
+        sparse_matrix::{SparseLayout, SparseMatrix},
+    },
+};
+
+use super::EXACT_TOLERANCE;
+
+#[derive(Debug, Args, Serialize, Deserialize)]
+pub struct RandomizedSvdCommand {
+    ///Path of a matrix file (.csv, .bin or .mtx) to use instead of a generated matrix
+    #[clap(short, long)]
+    input: Option<String>,
+    ///Amount of rows of the generated matrix
+    #[clap(short = 'm', long, default_value = "500")]
+    rows: usize,
+    ///Amount of columns of the generated matrix
+    #[clap(short = 'n', long, default_value = "100")]
+    columns: usize,
+    ///Rank of the approximation
+    #[clap(short, long, default_value = "10")]
+    k: usize,
+    ///Amount of extra samples taken by the range finder
+    #[clap(short, long, default_value = "10")]
+    oversampling: usize,
+    ///Amount of power iterations applied by the range finder
+    #[clap(short = 'q', long, default_value = "1")]
+    power_iterations: usize,
+    ///Standard deviation of the full rank noise added to the generated low rank matrix
+    #[clap(long, default_value = "0.01")]
+    noise: f64,
+    ///Kind of random projection used as the test matrix
+    #[clap(short, long, arg_enum, default_value = "gaussian")]
+    projection: ProjectionKind,
+    ///Amount of times the algorithm will run
+    #[clap(short, long, default_value = "20")]
+    repeat_count: usize,
+}
+
+impl RandomizedSvdCommand {
+    pub fn invoke(&self) -> Result<RunReport> {
+        self.algorithm()?.run()
+    }
+
+    pub fn algorithm(
+        &self,
+    ) -> Result<impl Algorithm<Output = impl Serialize> + Sync + Send + 'static> {
+        validate_count(name_of!(repeat_count in Self), self.repeat_count)?;
+
+        let rng = &mut random::rng();
+        let sparse = match &self.input {
+            Some(path) if matches!(MatrixFormat::from_path(path)?, MatrixFormat::MatrixMarket) => {
+                Some(SparseMatrix::read_file(path, SparseLayout::Csr)?)
+            }
+            _ => None,
+        };
+        let a = match (&self.input, &sparse) {
+            (_, Some(sparse)) => sparse.to_dense(),
+            (Some(path), None) => Matrix::read_file(path)?,
+            // a rank k matrix perturbed by full rank gaussian noise
+            (None, None) => {
+                (&(&Matrix::random_gaussian(self.rows, self.k, 1.0, rng)?
+                    * &Matrix::random_gaussian(self.k, self.columns, 1.0, rng)?)?
+                    + &Matrix::random_gaussian(self.rows, self.columns, self.noise, rng)?)?
+            }
+        };
+
+        if self.k + self.oversampling > a.row_count().min(a.column_count()) {
+            return Err(CliError::invalid_parameter(
+                name_of!(oversampling in Self),
+                self.oversampling,
+                &format!(
+                    "rank ({}) plus oversampling must not exceed the matrix dimensions {:?}",
+                    self.k,
+                    a.shape()
+                ),
+            )
+            .into());
+        }
+
+        let singular_values = a.svd()?.singular_values;
+        let optimal_frobenius_error = singular_values
+            .iter()
+            .skip(self.k)
+            .map(|value| value.powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let optimal_spectral_error = singular_values.get(self.k).copied().unwrap_or(0.0);
+
+        info!(
+            "exact svd computed [{}={} {}={}]",
+            name_of!(optimal_frobenius_error),
+            optimal_frobenius_error,
+            name_of!(optimal_spectral_error),
+            optimal_spectral_error
+        );
+
+        let largest_singular_value = singular_values.first().copied().unwrap_or(0.0);
+
+        if optimal_spectral_error <= EXACT_TOLERANCE * largest_singular_value {
+            return Err(CliError::Numeric(format!(
+                "the matrix has rank at most k, so the optimal error is 0 and error ratios are undefined [{}={}]",
+                name_of!(k in Self),
+                self.k
+            ))
+            .into());
+        }
+
+        // Matrix Market inputs stay sparse for the range finder, the dense copy is only
+        // used for the errors. The transpose is kept in CSR so both products gather rows
+        // in parallel
+        let (operator, transpose_operator): (Box<dyn LinearOperator>, Box<dyn LinearOperator>) =
+            match sparse {
+                Some(sparse) => {
+                    let nonzero_count = sparse.nonzero_count();
+
+                    info!(
+                        "sparse matrix loaded [{}={}]",
+                        name_of!(nonzero_count),
+                        nonzero_count
+                    );
+
+                    let transpose = sparse.with_layout(SparseLayout::Csc)?.transpose();
+
+                    (Box::new(sparse), Box::new(transpose))
+                }
+                None => (Box::new(a.clone()), Box::new(a.transpose())),
+            };
+
+        Ok(RandomizedSvdAlgorithm {
+            input: RandomizedSvdAlgorithmInput {
+                k: self.k,
+                oversampling: self.oversampling,
+                power_iterations: self.power_iterations,
+                projection: self.projection,
+            },
+            a,
+            operator,
+            transpose_operator,
+            optimal_frobenius_error,
+            optimal_spectral_error,
+        }
+        .repeat(self.repeat_count)
+        .reduce(|series, (input, _)| {
+            let count = series.len() as f64;
+
+            Ok(RandomizedSvdResult {
+                projection: input.projection,
+                average_frobenius_error_ratio: series
+                    .iter()
+                    .map(|estimate| estimate.frobenius_error_ratio)
+                    .sum::<f64>()
+                    / count,
+                max_frobenius_error_ratio: series
+                    .iter()
+                    .map(|estimate| estimate.frobenius_error_ratio)
+                    .fold(0.0, f64::max),
+                average_spectral_error_ratio: series
+                    .iter()
+                    .map(|estimate| estimate.spectral_error_ratio)
+                    .sum::<f64>()
+                    / count,
+            })
+        })
+        .with_name("Randomized SVD Algorithm".into()))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RandomizedSvdAlgorithmInput {
+    k: usize,
+    oversampling: usize,
+    power_iterations: usize,
+    projection: ProjectionKind,
+}
+
+#[derive(Debug)]
+struct RandomizedSvdEstimate {
+    /// `|A - A'|_F / |A - A_k|_F` for the randomized approximation A' and the optimal rank k approximation A_k
+    frobenius_error_ratio: f64,
+    /// `|A - A'|_2 / |A - A_k|_2`
+    spectral_error_ratio: f64,
+}
+
+struct RandomizedSvdAlgorithm {
+    input: RandomizedSvdAlgorithmInput,
+    a: Matrix<f64>,
+    /// A, the range finder only needs its products with dense matrices
+    operator: Box<dyn LinearOperator>,
+    /// A^T
+    transpose_operator: Box<dyn LinearOperator>,
+    optimal_frobenius_error: f64,
+    optimal_spectral_error: f64,
+}
+
+impl RandomizedSvdAlgorithm {
+    /// Orthonormal basis Q for the approximate range of A (Halko-Martinsson-Tropp, algorithm 4.4)
+    fn range_finder(&self) -> Result<Matrix<f64>> {
+        let test_matrix = self
+            .input
+            .projection
+            .generate(
+                self.input.k + self.input.oversampling,
+                self.a.column_count(),
+                &mut random::rng(),
+            )?
+            .transpose();
+
+        let mut q = self.operator.multiply_matrix(&test_matrix)?.qr()?.q;
+
+        for _ in 0..self.input.power_iterations {
+            let z = self.transpose_operator.multiply_matrix(&q)?.qr()?.q;
+
+            q = self.operator.multiply_matrix(&z)?.qr()?.q;
+        }
+
+        Ok(q)
+    }
+}
+
+impl Algorithm for RandomizedSvdAlgorithm {
+    type Input = RandomizedSvdAlgorithmInput;
+    type Output = RandomizedSvdEstimate;
+
+    fn name(&self) -> String {
+        "Randomized SVD Algorithm".into()
+    }
+
+    fn input(&self) -> Self::Input {
+        self.input.clone()
+    }
+
+    fn run_internal<F: Fn() + Send + Sync>(
+        &self,
+        update_progress: F,
+    ) -> Result<RandomizedSvdEstimate> {
+        debug!("run_internal started");
+
+        let q = self.range_finder()?;
+
+        // the svd of the small matrix B = Q^T A = (A^T Q)^T lifts to an approximate svd of A = QB
+        let mut svd = self
+            .transpose_operator
+            .multiply_matrix(&q)?
+            .transpose()
+            .svd()?;
+        svd.u = (&q * &svd.u)?;
+
+        let error = (&self.a - &svd.truncated(self.input.k)?)?;
+
+        let frobenius_error_ratio = error.frobenius_norm() / self.optimal_frobenius_error;
+        let spectral_error_ratio = error.operator_norm() / self.optimal_spectral_error;
+
+        debug!(
+            "run_internal finished [{}={}]",
+            name_of!(frobenius_error_ratio),
+            frobenius_error_ratio
+        );
+
+        update_progress();
+
+        Ok(RandomizedSvdEstimate {
+            frobenius_error_ratio,
+            spectral_error_ratio,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RandomizedSvdResult {
+    projection: ProjectionKind,
+    average_frobenius_error_ratio: f64,
+    max_frobenius_error_ratio: f64,
+    average_spectral_error_ratio: f64,
+}
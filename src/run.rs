@@ -0,0 +1,175 @@
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    common::{
+        algorithm::Algorithm,
+        experiment::Experiment,
+        random,
+        registry::{Parameters, Registry},
+        run_report::RunReport,
+    },
+    error::CliError,
+    q1, q2, q3, rnla,
+};
+
+#[derive(Debug, Args, Serialize)]
+pub struct RunCommand {
+    ///Name of the registered algorithm or path of a TOML or JSON experiment file to run, the registered algorithms are listed when omitted
+    target: Option<String>,
+    ///Parameter of the algorithm as name=value, overrides its default and the experiment file value
+    #[clap(short, long, value_name = "NAME=VALUE")]
+    set: Vec<String>,
+}
+
+impl RunCommand {
+    pub fn invoke(&self) -> Result<Option<RunReport>> {
+        let registry = registry();
+
+        let experiment = match &self.target {
+            Some(target) if Experiment::is_experiment_file(target) => Experiment::load(target)?,
+            Some(target) => Experiment {
+                algorithm: target.clone(),
+                parameters: Parameters::new(),
+                seed: None,
+                output: None,
+            },
+            None => {
+                for (name, description) in registry.entries() {
+                    println!("{:<24}{}", name, description);
+                }
+
+                return Ok(None);
+            }
+        };
+
+        let parameters = self.parameters(experiment.parameters)?;
+
+        // the --seed flag is applied before the command runs and takes precedence
+        if let (None, Some(seed)) = (random::seed(), experiment.seed) {
+            random::set_seed(seed);
+        }
+
+        // a seed of the experiment file is only known here, so generating the input and running
+        // the algorithm get their own scope
+        let (arguments, mut report) = random::scoped(random::seed(), || -> Result<_> {
+            let (algorithm, arguments) = registry.create(&experiment.algorithm, &parameters)?;
+
+            Ok((arguments, algorithm.run()?))
+        })?;
+        report.parameters = json!({
+            "algorithm": experiment.algorithm,
+            "parameters": arguments,
+        });
+        report.destination = experiment.output;
+
+        Ok(Some(report))
+    }
+
+    /// Parameters of the experiment file overridden by the `--set` values, either may name a
+    /// parameter after its flag
+    fn parameters(&self, experiment: Parameters) -> Result<Parameters> {
+        // later values override earlier ones
+        Ok(experiment
+            .into_iter()
+            .chain(self.set_values()?)
+            .map(|(name, value)| (name.replace('-', "_"), value))
+            .collect())
+    }
+
+    /// `--set` values are parsed as JSON and kept as strings when they are not valid JSON
+    fn set_values(&self) -> Result<Parameters> {
+        self.set
+            .iter()
+            .map(|parameter| match parameter.split_once('=') {
+                Some((name, value)) => Ok((
+                    name.to_string(),
+                    serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.into())),
+                )),
+                None => Err(CliError::invalid_parameter(
+                    "set",
+                    parameter,
+                    "must be formatted as name=value",
+                )
+                .into()),
+            })
+            .collect()
+    }
+}
+
+/// Every algorithm selectable by name with its default parameters
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+
+    q1::register(&mut registry);
+    q2::register(&mut registry);
+    q3::register(&mut registry);
+    rnla::register(&mut registry);
+
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(set: &[&str]) -> RunCommand {
+        RunCommand {
+            target: None,
+            set: set.iter().map(|parameter| parameter.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn set_values_are_parsed_as_json_or_kept_as_strings() {
+        let parameters = command(&["epsilon=0.25", "sparse=true", "projection=fast-jl"])
+            .parameters(Parameters::new())
+            .unwrap();
+
+        assert_eq!(
+            Value::Object(parameters),
+            json!({ "epsilon": 0.25, "sparse": true, "projection": "fast-jl" })
+        );
+        assert!(command(&["epsilon"]).parameters(Parameters::new()).is_err());
+    }
+
+    #[test]
+    fn set_values_override_the_experiment_file() {
+        let experiment = match json!({ "array-length": 200, "repeat_count": 300, "epsilon": 0.5 }) {
+            Value::Object(parameters) => parameters,
+            _ => unreachable!(),
+        };
+        let parameters = command(&["array_length=50", "repeat-count=10"])
+            .parameters(experiment)
+            .unwrap();
+
+        assert_eq!(
+            Value::Object(parameters),
+            json!({ "array_length": 50, "repeat_count": 10, "epsilon": 0.5 })
+        );
+    }
+
+    #[test]
+    fn registered_algorithms_are_built_from_the_overridden_arguments() {
+        let experiment = match json!({ "repeat_count": 300 }) {
+            Value::Object(parameters) => parameters,
+            _ => unreachable!(),
+        };
+        let parameters = command(&["array-length=50"])
+            .parameters(experiment)
+            .unwrap();
+        let (_, arguments) = registry().create("q1", &parameters).unwrap();
+
+        assert_eq!(arguments["array_length"], json!(50));
+        assert_eq!(arguments["repeat_count"], json!(300));
+        assert_eq!(arguments["max_value"], json!(9999));
+
+        let unknown = command(&["epsilon=0.5"])
+            .parameters(Parameters::new())
+            .unwrap();
+
+        assert!(registry().create("q1", &unknown).is_err());
+    }
+}